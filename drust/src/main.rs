@@ -27,5 +27,5 @@ impl ModuleTrait for Drust {
 
 #[pagetop::main]
 async fn main() -> std::io::Result<()> {
-    Application::prepare(&Drust).unwrap().run()?.await
+    Application::cli(&Drust).await
 }
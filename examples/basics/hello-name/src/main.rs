@@ -6,11 +6,10 @@ impl_handle!(APP_HELLO_NAME for HelloName);
 
 impl ModuleTrait for HelloName {
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.service(hello_name);
+        service_for_route!(scfg, get "/hello/{name}" => hello_name);
     }
 }
 
-#[service::get("/hello/{name}")]
 async fn hello_name(
     request: service::HttpRequest,
     path: service::web::Path<String>,
@@ -6,7 +6,7 @@ impl_handle!(APP_HELLO_WORLD for HelloWorld);
 
 impl ModuleTrait for HelloWorld {
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.route("/", service::web::get().to(hello_world));
+        service_for_route!(scfg, get "/" => hello_world);
     }
 }
 
@@ -9,6 +9,8 @@ pub struct Admin;
 impl_handle!(MODULE_ADMIN for Admin);
 
 impl ModuleTrait for Admin {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::t("module_name", &LOCALES_ADMIN)
     }
@@ -26,9 +28,7 @@ impl ModuleTrait for Admin {
     }
 
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.service(
-            service::web::scope("/admin").route("", service::web::get().to(summary::summary)),
-        );
+        service_for_route!(scfg, get "/admin" => summary::summary);
     }
 }
 
@@ -9,6 +9,8 @@ pub struct Bootsier;
 impl_handle!(THEME_BOOTSIER for Bootsier);
 
 impl ModuleTrait for Bootsier {
+    module_version!();
+
     fn theme(&self) -> Option<ThemeRef> {
         Some(&Bootsier)
     }
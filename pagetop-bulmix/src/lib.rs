@@ -7,6 +7,8 @@ pub struct Bulmix;
 impl_handle!(THEME_BULMIX for Bulmix);
 
 impl ModuleTrait for Bulmix {
+    module_version!();
+
     fn theme(&self) -> Option<ThemeRef> {
         Some(&Bulmix)
     }
@@ -9,6 +9,8 @@ pub struct HomeDemo;
 impl_handle!(MODULE_HOMEDEMO for HomeDemo);
 
 impl ModuleTrait for HomeDemo {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::t("module_name", &LOCALES_HOMEDEMO)
     }
@@ -19,7 +21,7 @@ impl ModuleTrait for HomeDemo {
 
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
         service_for_static_files!(scfg, homedemo => "/homedemo");
-        scfg.route("/", service::web::get().to(demo));
+        service_for_route!(scfg, get "/" => demo);
     }
 }
 
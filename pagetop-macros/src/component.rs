@@ -0,0 +1,337 @@
+use proc_macro2::TokenStream;
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    parse_quote, Attribute, Data, DeriveInput, Fields, Ident, ImplItem, ItemImpl, Path, Token,
+};
+
+// Argumento de un atributo `#[component(...)]`: una clave con o sin valor, como `id` o
+// `handle = COMPONENT_BASE_BLOCK`.
+struct ComponentArg {
+    key: Ident,
+    value: Option<Path>,
+}
+
+impl Parse for ComponentArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key: Ident = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse::<Path>()?)
+        } else {
+            None
+        };
+        Ok(ComponentArg { key, value })
+    }
+}
+
+fn component_args(attrs: &[Attribute]) -> syn::Result<Vec<ComponentArg>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("component")) {
+        args.extend(attr.parse_args_with(Punctuated::<ComponentArg, Token![,]>::parse_terminated)?);
+    }
+    Ok(args)
+}
+
+// Ruta al crate `pagetop` desde el crate que usa las macros.
+fn pagetop_crate() -> TokenStream {
+    match crate_name("pagetop").expect("pagetop is present in `Cargo.toml`") {
+        FoundCrate::Itself => quote!(crate),
+        FoundCrate::Name(name) => {
+            let name = format_ident!("{}", name);
+            quote!(::#name)
+        }
+    }
+}
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let pagetop = pagetop_crate();
+    let name = &input.ident;
+
+    // Atributos del componente.
+    let mut handle = None;
+    for arg in component_args(&input.attrs)?.into_iter() {
+        match (arg.key.to_string().as_str(), arg.value) {
+            ("handle", Some(value)) => handle = Some(value),
+            ("handle", None) => return Err(syn::Error::new(arg.key.span(), "expected a value")),
+            _ => return Err(syn::Error::new(arg.key.span(), "expected `handle`")),
+        }
+    }
+
+    // Atributos de los campos.
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "#[derive(Component)] requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "#[derive(Component)] requires a struct with named fields",
+            ))
+        }
+    };
+    let mut id = None;
+    let mut weight = None;
+    let mut renderable = None;
+    let mut classes = None;
+    let mut template = None;
+    for field in fields.iter() {
+        for arg in component_args(&field.attrs)?.into_iter() {
+            let slot = match arg.key.to_string().as_str() {
+                "id" => &mut id,
+                "weight" => &mut weight,
+                "renderable" => &mut renderable,
+                "classes" => &mut classes,
+                "template" => &mut template,
+                _ => {
+                    return Err(syn::Error::new(
+                        arg.key.span(),
+                        "expected `id`, `weight`, `renderable`, `classes` or `template`",
+                    ))
+                }
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(arg.key.span(), "duplicate field attribute"));
+            }
+            *slot = field.ident.clone();
+        }
+    }
+
+    // Identificador del componente.
+    let handle_const = handle.as_ref().map(|handle| {
+        quote! {
+            /// Constant handle to represent a unique PageTop building element.
+            pub const #handle: #pagetop::Handle =
+                #pagetop::util::handle(module_path!(), file!(), line!(), column!());
+        }
+    });
+    let handle_value = match &handle {
+        Some(handle) => quote!(#handle),
+        None => quote! {{
+            const HANDLE: #pagetop::Handle =
+                #pagetop::util::handle(module_path!(), file!(), line!(), column!());
+            HANDLE
+        }},
+    };
+
+    // Valores de los campos para los métodos de `ComponentTrait`.
+    let field_id = match &id {
+        Some(f) => quote!(self.#f.get()),
+        None => quote!(None),
+    };
+    let field_weight = match &weight {
+        Some(f) => quote!(self.#f),
+        None => quote!(0),
+    };
+    let field_is_renderable = match &renderable {
+        Some(f) => quote!((self.#f.check)(cx)),
+        None => quote!(true),
+    };
+
+    // Constructores y métodos de acceso.
+    let builder_id = id.as_ref().map(|f| {
+        quote! {
+            pub fn with_id(mut self, id: impl Into<String>) -> Self {
+                self.alter_id(id);
+                self
+            }
+
+            pub fn alter_id(&mut self, id: impl Into<String>) -> &mut Self {
+                self.#f.alter_value(id);
+                self
+            }
+        }
+    });
+    let builder_weight = weight.as_ref().map(|f| {
+        quote! {
+            pub fn with_weight(mut self, value: #pagetop::Weight) -> Self {
+                self.alter_weight(value);
+                self
+            }
+
+            pub fn alter_weight(&mut self, value: #pagetop::Weight) -> &mut Self {
+                self.#f = value;
+                self
+            }
+        }
+    });
+    let builder_renderable = renderable.as_ref().map(|f| {
+        quote! {
+            pub fn with_renderable(
+                mut self,
+                check: #pagetop::core::component::FnIsRenderable,
+            ) -> Self {
+                self.alter_renderable(check);
+                self
+            }
+
+            pub fn alter_renderable(
+                &mut self,
+                check: #pagetop::core::component::FnIsRenderable,
+            ) -> &mut Self {
+                self.#f.check = check;
+                self
+            }
+        }
+    });
+    let builder_classes = classes.as_ref().map(|f| {
+        quote! {
+            pub fn with_classes(
+                mut self,
+                op: #pagetop::html::ClassesOp,
+                classes: impl Into<String>,
+            ) -> Self {
+                self.alter_classes(op, classes);
+                self
+            }
+
+            pub fn alter_classes(
+                &mut self,
+                op: #pagetop::html::ClassesOp,
+                classes: impl Into<String>,
+            ) -> &mut Self {
+                self.#f.alter_value(op, classes);
+                self
+            }
+
+            pub fn classes(&self) -> &#pagetop::html::OptionClasses {
+                &self.#f
+            }
+        }
+    });
+    let builder_template = template.as_ref().map(|f| {
+        quote! {
+            pub fn with_template(mut self, template: &str) -> Self {
+                self.alter_template(template);
+                self
+            }
+
+            pub fn alter_template(&mut self, template: &str) -> &mut Self {
+                self.#f = template.to_owned();
+                self
+            }
+
+            pub fn template(&self) -> &str {
+                self.#f.as_str()
+            }
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #handle_const
+
+        impl #impl_generics #pagetop::HasHandle for #name #ty_generics #where_clause {
+            #[inline]
+            fn static_handle() -> #pagetop::Handle {
+                #handle_value
+            }
+
+            #[inline]
+            fn handle(&self) -> #pagetop::Handle {
+                #handle_value
+            }
+        }
+
+        impl #impl_generics #pagetop::core::component::ComponentFields for #name #ty_generics
+            #where_clause
+        {
+            #[inline]
+            fn field_id(&self) -> Option<String> {
+                #field_id
+            }
+
+            #[inline]
+            fn field_weight(&self) -> #pagetop::Weight {
+                #field_weight
+            }
+
+            #[inline]
+            #[allow(unused_variables)]
+            fn field_is_renderable(&self, cx: &#pagetop::core::component::Context) -> bool {
+                #field_is_renderable
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #builder_id
+            #builder_weight
+            #builder_renderable
+            #builder_classes
+            #builder_template
+        }
+    })
+}
+
+pub fn expand_trait(mut item: ItemImpl) -> syn::Result<TokenStream> {
+    let pagetop = pagetop_crate();
+    if item.trait_.is_none() {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "#[component_trait] requires an `impl ComponentTrait for ...` block",
+        ));
+    }
+
+    // Métodos que ya implementa el componente.
+    let implemented: Vec<String> = item
+        .items
+        .iter()
+        .filter_map(|i| match i {
+            ImplItem::Method(method) => Some(method.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+
+    // Añade los métodos que faltan a partir de los campos del componente.
+    let fields = quote!(#pagetop::core::component::ComponentFields);
+    let methods: [(&str, ImplItem); 4] = [
+        (
+            "new",
+            parse_quote! {
+                fn new() -> Self {
+                    <Self as ::core::default::Default>::default()
+                }
+            },
+        ),
+        (
+            "id",
+            parse_quote! {
+                fn id(&self) -> Option<String> {
+                    #fields::field_id(self)
+                }
+            },
+        ),
+        (
+            "weight",
+            parse_quote! {
+                fn weight(&self) -> #pagetop::Weight {
+                    #fields::field_weight(self)
+                }
+            },
+        ),
+        (
+            "is_renderable",
+            parse_quote! {
+                fn is_renderable(&self, cx: &#pagetop::core::component::Context) -> bool {
+                    #fields::field_is_renderable(self, cx)
+                }
+            },
+        ),
+    ];
+    for (name, method) in methods.into_iter() {
+        if !implemented.iter().any(|i| i == name) {
+            item.items.push(method);
+        }
+    }
+
+    Ok(quote!(#item))
+}
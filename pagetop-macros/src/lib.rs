@@ -1,10 +1,11 @@
+mod component;
 mod maud;
 
 use concat_string::concat_string;
 use proc_macro::TokenStream;
 use proc_macro_error::proc_macro_error;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{parse_macro_input, parse_str, ItemFn};
+use syn::{parse_macro_input, parse_quote, parse_str, DeriveInput, ItemFn, ItemImpl, ReturnType};
 
 #[proc_macro]
 #[proc_macro_error]
@@ -57,6 +58,9 @@ pub fn fn_builder(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Marks async main function as the PageTop entry-point.
 ///
+/// When the function body finishes, `Application::shutdown()` is awaited so that modules are shut
+/// down after the web server stops gracefully.
+///
 /// # Examples
 /// ```
 /// #[pagetop::main]
@@ -66,13 +70,24 @@ pub fn fn_builder(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro_attribute]
 pub fn main(_: TokenStream, item: TokenStream) -> TokenStream {
-    let mut output: TokenStream = (quote! {
-        #[::pagetop::service::rt::main(system = "::pagetop::service::rt::System")]
-    })
-    .into();
+    let mut fn_item = parse_macro_input!(item as ItemFn);
 
-    output.extend(item);
-    output
+    let body = &fn_item.block;
+    let output = match &fn_item.sig.output {
+        ReturnType::Default => quote! { () },
+        ReturnType::Type(_, ty) => quote! { #ty },
+    };
+    fn_item.block = parse_quote! {{
+        let result: #output = async move #body.await;
+        ::pagetop::app::Application::shutdown().await;
+        result
+    }};
+
+    let expanded = quote! {
+        #[::pagetop::service::rt::main(system = "::pagetop::service::rt::System")]
+        #fn_item
+    };
+    expanded.into()
 }
 
 /// Marks async test functions to use the PageTop entry-point.
@@ -94,3 +109,62 @@ pub fn test(_: TokenStream, item: TokenStream) -> TokenStream {
     output.extend(item);
     output
 }
+
+/// Implements the boilerplate of a PageTop component.
+///
+/// Generates the `HasHandle` implementation and the builders and getters of the fields marked with
+/// `#[component(id)]`, `#[component(weight)]`, `#[component(renderable)]`,
+/// `#[component(classes)]` or `#[component(template)]`. The optional container attribute
+/// `handle = NAME` declares a public constant handle.
+///
+/// It also implements `ComponentFields`, which [`macro@component_trait`] uses to complete the
+/// `ComponentTrait` implementation with `new()`, `id()`, `weight()` and `is_renderable()`.
+///
+/// # Examples
+/// ```ignore
+/// use pagetop::prelude::*;
+///
+/// #[derive(Component, Default)]
+/// #[component(handle = COMPONENT_NOTE)]
+/// pub struct Note {
+///     #[component(id)]
+///     id: OptionId,
+///     #[component(weight)]
+///     weight: Weight,
+///     #[component(classes)]
+///     classes: OptionClasses,
+///     text: String,
+/// }
+///
+/// #[component_trait]
+/// impl ComponentTrait for Note {
+///     fn prepare_component(&self, _cx: &mut Context) -> PrepareMarkup {
+///         PrepareMarkup::With(html! {
+///             div id=[self.id()] class=[self.classes().get()] { (self.text) }
+///         })
+///     }
+/// }
+///
+/// let note = Note::new().with_id("note").with_weight(-1);
+/// ```
+#[proc_macro_derive(Component, attributes(component))]
+pub fn derive_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    component::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Completes the `ComponentTrait` implementation of a component that derives [`Component`].
+///
+/// Adds the `new()`, `id()`, `weight()` and `is_renderable()` methods that the block does not
+/// implement. `new()` returns `Default::default()` and the others use the fields marked in the
+/// derive. Any other method, such as `prepare_component()` or `render_cache()`, is implemented as
+/// usual.
+#[proc_macro_attribute]
+pub fn component_trait(_: TokenStream, item: TokenStream) -> TokenStream {
+    let item = parse_macro_input!(item as ItemImpl);
+    component::expand_trait(item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
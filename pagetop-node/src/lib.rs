@@ -10,6 +10,8 @@ pub struct Node;
 impl_handle!(MODULE_NODE for Node);
 
 impl ModuleTrait for Node {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::t("module_name", &LOCALES_NODE)
     }
@@ -19,7 +21,7 @@ impl ModuleTrait for Node {
     }
 
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.route("/node", service::web::get().to(node));
+        service_for_route!(scfg, get "/node" => node);
     }
 
     fn actions(&self) -> Vec<Action> {
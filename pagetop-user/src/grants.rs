@@ -0,0 +1,96 @@
+use pagetop::prelude::*;
+
+#[derive(Iden)]
+enum Role {
+    Table,
+    Rid,
+    Name,
+}
+
+#[derive(Iden)]
+enum RolePermission {
+    Table,
+    Rid,
+    Permission,
+}
+
+// Permisos concedidos a cada rol, guardados en la tabla `role_permission`.
+pub struct RolePermissions;
+
+#[async_trait::async_trait(?Send)]
+impl service::GrantStore for RolePermissions {
+    async fn load(&self) -> Result<Vec<(String, String)>, String> {
+        let rows = db::query::<SelectStatement>(
+            Query::select()
+                .column((Role::Table, Role::Name))
+                .column((RolePermission::Table, RolePermission::Permission))
+                .from(RolePermission::Table)
+                .inner_join(
+                    Role::Table,
+                    Expr::col((Role::Table, Role::Rid))
+                        .equals((RolePermission::Table, RolePermission::Rid)),
+                ),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut grants = Vec::new();
+        for row in rows.iter() {
+            let role: String = row.try_get("", "name").map_err(|e| e.to_string())?;
+            let permission: String = row.try_get("", "permission").map_err(|e| e.to_string())?;
+            grants.push((role, permission));
+        }
+        Ok(grants)
+    }
+
+    async fn grant(&self, role: &str, permission: &str) -> Result<(), String> {
+        check_role(role).await?;
+        self.revoke(role, permission).await?;
+        db::exec::<InsertStatement>(
+            Query::insert()
+                .into_table(RolePermission::Table)
+                .columns([RolePermission::Rid, RolePermission::Permission])
+                .select_from(role_rid(role).expr(Expr::val(permission)).to_owned())
+                .map_err(|e| e.to_string())?,
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+
+    async fn revoke(&self, role: &str, permission: &str) -> Result<(), String> {
+        db::exec::<DeleteStatement>(
+            Query::delete()
+                .from_table(RolePermission::Table)
+                .and_where(Expr::col(RolePermission::Permission).eq(permission))
+                .and_where(Expr::col(RolePermission::Rid).in_subquery(role_rid(role))),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+}
+
+fn role_rid(role: &str) -> SelectStatement {
+    Query::select()
+        .column(Role::Rid)
+        .from(Role::Table)
+        .and_where(Expr::col(Role::Name).eq(role))
+        .to_owned()
+}
+
+async fn check_role(role: &str) -> Result<(), String> {
+    let rows = db::query::<SelectStatement>(
+        Query::select()
+            .column(Role::Name)
+            .from(Role::Table)
+            .and_where(Expr::col(Role::Name).eq(role)),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    match rows.is_empty() {
+        true => Err(format!("Role \"{}\" does not exist", role)),
+        false => Ok(()),
+    }
+}
@@ -1,7 +1,10 @@
 use pagetop::prelude::*;
 
+use serde::Deserialize;
+
 static_locales!(LOCALES_USER);
 
+mod grants;
 mod migration;
 
 pub struct User;
@@ -9,6 +12,8 @@ pub struct User;
 impl_handle!(MODULE_USER for User);
 
 impl ModuleTrait for User {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::t("module_name", &LOCALES_USER)
     }
@@ -17,8 +22,20 @@ impl ModuleTrait for User {
         L10n::t("module_description", &LOCALES_USER)
     }
 
+    fn permissions(&self) -> Vec<Permission> {
+        vec![Permission::new("administer users")
+            .with_title(L10n::t("perm_administer_users", &LOCALES_USER))
+            .with_description(L10n::t("perm_administer_users_description", &LOCALES_USER))]
+    }
+
+    fn init(&self) {
+        service::set_grant_store(grants::RolePermissions);
+    }
+
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-        scfg.route("/user/login", service::web::get().to(login));
+        service_for_route!(scfg, get "/user/login" => login);
+        service_for_route!(scfg, post "/user/login" => login_submit);
+        service::add_rate_limit("/user/login", service::RateLimit::new(10, 60));
     }
 
     fn migrations(&self) -> Vec<MigrationItem> {
@@ -43,6 +60,22 @@ async fn login(request: service::HttpRequest) -> ResultPage<Markup, FatalError>
         .render()
 }
 
+#[derive(Deserialize)]
+pub struct LoginForm {
+    pub name: String,
+    pub pass: String,
+}
+
+// Sólo se atienden envíos con un token anti-falsificación válido. La autenticación todavía no está
+// implementada, así que se vuelve a mostrar el formulario.
+async fn login_submit(
+    request: service::HttpRequest,
+    form: service::CsrfForm<LoginForm>,
+) -> ResultPage<Markup, FatalError> {
+    trace::debug!("Login attempt for user \"{}\"", form.name);
+    login(request).await
+}
+
 fn form_login() -> Form {
     Form::new()
         .with_id("user-login")
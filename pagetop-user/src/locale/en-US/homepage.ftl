@@ -6,3 +6,6 @@ password = Password
 username_help = Enter your { $app } username.
 password_help = Enter the password that accompanies your username.
 login = Log in
+
+perm_administer_users = Administer users
+perm_administer_users_description = Manage user accounts and their roles.
@@ -6,3 +6,6 @@ password = Contraseña
 username_help = Introduzca su nombre de usuario en { $app }.
 password_help = Introduzca la contraseña asociada a su nombre de usuario.
 login = Iniciar sesión
+
+perm_administer_users = Administrar usuarios
+perm_administer_users_description = Gestionar las cuentas de usuario y sus roles.
@@ -1,7 +1,25 @@
 //! Instancia y ejecuta una aplicación creada con **PageTop**.
 
+mod cli;
+
+mod error;
+pub use error::PrepareError;
+
 mod figfont;
 
+mod health;
+
+mod maintenance;
+pub use maintenance::{allow_in_maintenance, is_in_maintenance};
+
+mod security;
+
+mod session;
+use session::{SessionBackend, SessionKeys, SESSION_COOKIE_NAME};
+
+mod tls;
+
+use crate::base::action;
 use crate::core::{module, module::ModuleRef};
 use crate::html::Markup;
 use crate::response::fatal_error::FatalError;
@@ -12,75 +30,197 @@ use crate::{concat_string, config, locale, service, trace, LazyStatic};
 use crate::db;
 
 use actix_session::config::{BrowserSession, PersistentSession, SessionLifecycle};
-use actix_session::storage::CookieSessionStore;
 use actix_session::SessionMiddleware;
 
+use actix_web::dev::Service as _;
+
 use substring::Substring;
 
 use std::io::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-pub struct Application;
+// Indica si se ha arrancado el servidor web y aún no se han finalizado los módulos.
+static SERVER_STARTED: AtomicBool = AtomicBool::new(false);
+
+pub struct Application {
+    session_keys: SessionKeys,
+    session_backend: SessionBackend,
+    #[cfg(feature = "tls")]
+    tls_config: Option<rustls::ServerConfig>,
+}
 
 impl Application {
-    pub fn prepare(app: ModuleRef) -> Result<Self, Error> {
+    pub fn prepare(app: ModuleRef) -> Result<Self, PrepareError> {
         // On startup.
         show_banner();
 
         // Inicia registro de trazas y eventos.
         LazyStatic::force(&trace::TRACING);
 
-        // Valida el identificador global de idioma.
-        LazyStatic::force(&locale::LANGID);
-
-        #[cfg(feature = "database")]
-        // Conecta con la base de datos.
-        LazyStatic::force(&db::DBCONN);
+        // Valida las claves para las cookies de sesión.
+        let session_keys = SessionKeys::from_settings()?;
 
-        // Registra los módulos de la aplicación.
-        module::all::register_modules(app);
+        // Valida el certificado y la clave privada para HTTPS.
+        #[cfg(feature = "tls")]
+        let tls_config = tls::server_config()?;
+        #[cfg(not(feature = "tls"))]
+        tls::server_config()?;
 
-        // Registra acciones de los módulos.
-        module::all::register_actions();
+        // Registra e inicializa los módulos de la aplicación.
+        bootstrap(app)?;
 
-        // Inicializa los módulos.
-        module::all::init_modules();
+        // Prepara el almacén para los datos de sesión.
+        let session_backend = SessionBackend::from_settings()?;
 
         #[cfg(feature = "database")]
         // Ejecuta actualizaciones pendientes de la base de datos.
         module::all::run_migrations();
 
-        Ok(Self)
+        #[cfg(feature = "database")]
+        // Carga los permisos concedidos a cada rol.
+        if let Err(e) = db::run_now(service::load_grants()) {
+            trace::error!("Failed to load the permissions granted to roles ({})", e);
+        }
+
+        Ok(Self {
+            session_keys,
+            session_backend,
+            #[cfg(feature = "tls")]
+            tls_config,
+        })
+    }
+
+    /// Ejecuta la aplicación según los argumentos de la línea de comandos.
+    ///
+    /// Sin argumentos, o con `serve`, prepara y arranca el servidor web como
+    /// [`prepare()`](Self::prepare) y [`run()`](Self::run). Con `help` muestra todos los
+    /// subcomandos disponibles, incluidos los que añaden los módulos habilitados.
+    pub async fn cli(app: ModuleRef) -> Result<(), Error> {
+        cli::run(app, std::env::args().skip(1).collect()).await
     }
 
+    /// Arranca el servidor web.
+    ///
+    /// Cuando el servidor está escuchando se llama a [`on_server_started()`] de los módulos
+    /// habilitados. Al detenerse ordenadamente (por ejemplo, con SIGTERM) hay que llamar a
+    /// [`shutdown()`](Self::shutdown), como hace [`#[pagetop::main]`](crate::main) al terminar.
+    ///
+    /// [`on_server_started()`]: crate::core::module::ModuleTrait::on_server_started
     pub fn run(self) -> Result<service::Server, Error> {
-        // Generate cookie key.
-        let secret_key = service::cookie::Key::generate();
+        let session_keys = self.session_keys;
+        let session_backend = self.session_backend;
+
+        // Elimina periódicamente las sesiones caducadas.
+        session_backend.spawn_sweeper();
+
+        // Ejecuta las tareas programadas de los módulos.
+        module::all::spawn_scheduled_tasks();
 
         // Prepara el servidor web.
-        Ok(service::HttpServer::new(move || {
+        let server = service::HttpServer::new(move || {
+            let rotation_keys = session_keys.clone();
             service_app()
-                .wrap(tracing_actix_web::TracingLogger::default())
-                .wrap(
-                    SessionMiddleware::builder(CookieSessionStore::default(), secret_key.clone())
-                        .session_lifecycle(match config::SETTINGS.server.session_lifetime {
-                            0 => SessionLifecycle::BrowserSession(BrowserSession::default()),
-                            _ => SessionLifecycle::PersistentSession(
-                                PersistentSession::default().session_ttl(
-                                    service::cookie::time::Duration::seconds(
-                                        config::SETTINGS.server.session_lifetime,
-                                    ),
-                                ),
-                            ),
-                        })
-                        .build(),
-                )
-        })
-        .bind(format!(
+                .wrap_fn(|req, srv| {
+                    let response = match tls::redirect_to_https(&req) {
+                        Some(redirect) => Err(req.into_response(redirect)),
+                        None => Ok(srv.call(req)),
+                    };
+                    async move {
+                        match response {
+                            Ok(future) => future.await,
+                            Err(redirect) => Ok(redirect),
+                        }
+                    }
+                })
+                .wrap(tracing_actix_web::TracingLogger::<service::RequestIdRootSpan>::new())
+                .wrap(session_middleware(
+                    session_backend.clone(),
+                    session_keys.current().clone(),
+                ))
+                .wrap_fn(move |mut req, srv| {
+                    let rotated = rotation_keys.rotate_cookie(&mut req);
+                    let response = srv.call(req);
+                    async move {
+                        let mut response = response.await?;
+                        // Envía la cookie cifrada con la clave actual si la sesión no la renueva.
+                        if let Some(cookie) = rotated {
+                            if !response
+                                .response()
+                                .cookies()
+                                .any(|c| c.name() == SESSION_COOKIE_NAME)
+                            {
+                                response.response_mut().add_cookie(&cookie)?;
+                            }
+                        }
+                        Ok(response)
+                    }
+                })
+        });
+
+        // Ajusta el servidor web según la configuración.
+        let server = match config::SETTINGS.server.workers {
+            0 => server,
+            workers => server.workers(workers),
+        };
+        let server = server
+            .keep_alive(match config::SETTINGS.server.keep_alive {
+                0 => service::http::KeepAlive::Disabled,
+                seconds => service::http::KeepAlive::Timeout(Duration::from_secs(seconds)),
+            })
+            .client_request_timeout(Duration::from_millis(
+                config::SETTINGS.server.client_request_timeout,
+            ))
+            .shutdown_timeout(config::SETTINGS.server.shutdown_timeout)
+            .backlog(config::SETTINGS.server.backlog)
+            .max_connections(config::SETTINGS.server.max_connections);
+
+        let address = format!(
             "{}:{}",
             &config::SETTINGS.server.bind_address,
             &config::SETTINGS.server.bind_port
-        ))?
-        .run())
+        );
+
+        #[cfg(feature = "tls")]
+        let server = match self.tls_config {
+            Some(tls_config) => {
+                let server = server.bind_rustls_021(&address, tls_config)?;
+                match config::SETTINGS.server.redirect_port {
+                    0 => server,
+                    port => server.bind(format!(
+                        "{}:{}",
+                        &config::SETTINGS.server.bind_address,
+                        port
+                    ))?,
+                }
+            }
+            None => server.bind(&address)?,
+        };
+        #[cfg(not(feature = "tls"))]
+        let server = server.bind(&address)?;
+
+        let addrs = server.addrs();
+        let server = server.run();
+        SERVER_STARTED.store(true, Ordering::Relaxed);
+
+        // Notifica a los módulos que el servidor ya está escuchando.
+        service::rt::spawn(module::all::server_started(addrs));
+
+        Ok(server)
+    }
+
+    /// Finaliza la aplicación una vez detenido el servidor web.
+    ///
+    /// Si se arrancó el servidor con [`run()`](Self::run) llama a [`on_shutdown()`] de los módulos
+    /// habilitados en orden inverso. En cualquier caso envía las trazas y eventos pendientes. Sólo
+    /// la primera llamada tiene efecto.
+    ///
+    /// [`on_shutdown()`]: crate::core::module::ModuleTrait::on_shutdown
+    pub async fn shutdown() {
+        if SERVER_STARTED.swap(false, Ordering::Relaxed) {
+            module::all::shutdown_modules().await;
+        }
+        trace::shutdown();
     }
 
     pub fn test(
@@ -94,10 +234,28 @@ impl Application {
             InitError = (),
         >,
     > {
-        service_app()
+        service_app().wrap(session_middleware(
+            self.session_backend,
+            self.session_keys.current().clone(),
+        ))
     }
 }
 
+fn session_middleware(
+    session_backend: SessionBackend,
+    key: service::cookie::Key,
+) -> SessionMiddleware<SessionBackend> {
+    SessionMiddleware::builder(session_backend, key)
+        .cookie_name(SESSION_COOKIE_NAME.to_owned())
+        .session_lifecycle(match config::SETTINGS.server.session_lifetime {
+            0 => SessionLifecycle::BrowserSession(BrowserSession::default()),
+            _ => SessionLifecycle::PersistentSession(PersistentSession::default().session_ttl(
+                service::cookie::time::Duration::seconds(config::SETTINGS.server.session_lifetime),
+            )),
+        })
+        .build()
+}
+
 fn service_app() -> service::App<
     impl service::Factory<
         service::Request,
@@ -108,10 +266,99 @@ fn service_app() -> service::App<
     >,
 > {
     service::App::new()
+        .wrap_fn(|req, srv| {
+            let response = match maintenance::check(req) {
+                Ok(req) => Ok(srv.call(req)),
+                Err(unavailable) => Err(unavailable),
+            };
+            async move {
+                match response {
+                    Ok(future) => future.await,
+                    Err(unavailable) => Ok(*unavailable),
+                }
+            }
+        })
+        .wrap_fn(|req, srv| {
+            let response = match service::check_rate_limit(req) {
+                Ok(req) => Ok(srv.call(req)),
+                Err(too_many) => Err(too_many),
+            };
+            async move {
+                match response {
+                    Ok(future) => future.await,
+                    Err(too_many) => Ok(*too_many),
+                }
+            }
+        })
+        .wrap_fn(|req, srv| {
+            let response = srv.call(req);
+            async move {
+                let mut response = response.await?;
+                security::add_headers(&mut response);
+                Ok(response)
+            }
+        })
+        .wrap_fn(|req, srv| {
+            action::request::OnRequestStart::dispatch(req.request());
+            let response = srv.call(req);
+            async move {
+                let mut response = response.await?;
+                let request = response.request().clone();
+                action::request::OnResponseFinalize::dispatch(&request, response.headers_mut());
+                Ok(response)
+            }
+        })
+        .wrap_fn(|req, srv| {
+            let request_id = service::assign_request_id(&req);
+            let response = srv.call(req);
+            async move {
+                let mut response = response.await?;
+                service::add_request_id_header(&mut response, &request_id);
+                Ok(response)
+            }
+        })
+        .app_data(service::web::PayloadConfig::new(
+            config::SETTINGS.server.max_payload_size,
+        ))
+        .app_data(
+            service::web::FormConfig::default().limit(config::SETTINGS.server.max_payload_size),
+        )
+        .configure(health::configure_services)
         .configure(module::all::configure_services)
         .default_service(service::web::route().to(service_not_found))
 }
 
+// Inicializa lo necesario para registrar, inicializar y consultar los módulos de la aplicación,
+// sin validar ni preparar nada propio del servidor web.
+fn bootstrap(app: ModuleRef) -> Result<(), PrepareError> {
+    // Valida el identificador global de idioma.
+    LazyStatic::force(&locale::LANGID);
+
+    #[cfg(feature = "database")]
+    // Conecta con la base de datos.
+    db::connect()?;
+
+    // Registra los módulos de la aplicación.
+    module::all::register_modules(app)?;
+
+    // Registra acciones de los módulos.
+    module::all::register_actions();
+
+    // Registra los permisos de los módulos.
+    module::all::register_permissions();
+
+    // Registra las tareas programadas de los módulos.
+    module::all::register_scheduled_tasks();
+
+    // Inicializa los módulos.
+    module::all::init_modules();
+
+    // Comprueba que no hay identificadores repetidos.
+    module::all::check_handles()?;
+
+    Ok(())
+}
+
 async fn service_not_found(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
     Err(FatalError::NotFound(request))
 }
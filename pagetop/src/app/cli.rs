@@ -0,0 +1,454 @@
+use crate::core::action;
+use crate::core::module::{self, ModuleRef};
+use crate::core::theme::all::{THEME, THEMES};
+use crate::{config, service, trace, LazyStatic};
+
+use super::maintenance::{self, is_in_maintenance};
+use super::{bootstrap, service_app, Application};
+
+use std::io::{Error, ErrorKind};
+
+// Subcomandos propios de PageTop, con sus argumentos y descripción para la ayuda.
+const BUILTIN_COMMANDS: [(&str, &str, &str); 11] = [
+    ("serve", "", "Start the web server (default)"),
+    (
+        "migrate",
+        "up [steps] | down [steps] | status | fresh",
+        "Manage database migrations of the enabled modules",
+    ),
+    (
+        "config",
+        "show",
+        "Show the settings in use, with secrets redacted",
+    ),
+    (
+        "cron",
+        "run [task] | list",
+        "Run the scheduled tasks of the modules once, or list them",
+    ),
+    (
+        "maintenance",
+        "on | off | status",
+        "Switch maintenance mode using the flag file",
+    ),
+    (
+        "modules",
+        "",
+        "List enabled and dropped modules with their dependencies",
+    ),
+    (
+        "module",
+        "install | disable | uninstall <name>",
+        "Change the status of a module (applies on next start)",
+    ),
+    (
+        "actions",
+        "",
+        "List the actions registered by the enabled modules",
+    ),
+    ("routes", "", "List the routes of the enabled modules"),
+    ("themes", "", "List the available themes"),
+    ("help", "", "Show this help"),
+];
+
+// Valor que sustituye en `config show` a los ajustes con información sensible.
+const REDACTED: &str = "********";
+
+pub(crate) async fn run(app: ModuleRef, args: Vec<String>) -> Result<(), Error> {
+    let command = args.first().map(String::as_str).unwrap_or("serve");
+    let args = args.get(1..).unwrap_or_default();
+
+    match command {
+        "serve" => {
+            let result = Application::prepare(app)?.run()?.await;
+            Application::shutdown().await;
+            return result;
+        }
+        "config" => return config_command(args),
+        "maintenance" => return maintenance_command(args),
+        _ => {}
+    }
+
+    // Los demás subcomandos necesitan los módulos registrados e inicializados.
+    LazyStatic::force(&trace::TRACING);
+    bootstrap(app)?;
+
+    match command {
+        "cron" => cron_command(args),
+        "migrate" => migrate_command(args),
+        "module" => module_command(args),
+        "modules" => modules_command(),
+        "actions" => actions_command(),
+        "routes" => routes_command(),
+        "themes" => themes_command(),
+        "help" | "--help" | "-h" => {
+            print_help();
+            Ok(())
+        }
+        _ => {
+            for m in module::all::enabled_modules().iter() {
+                if let Some(c) = m.commands().iter().find(|c| c.name() == command) {
+                    return c.run(args);
+                }
+            }
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unknown command \"{}\". Try \"help\"", command),
+            ))
+        }
+    }
+}
+
+fn print_help() {
+    println!(
+        "{} (PageTop {})\n\nUsage: {} [command] [args...]\n\nCommands:",
+        config::SETTINGS.app.name,
+        env!("CARGO_PKG_VERSION"),
+        std::env::args().next().unwrap_or_else(|| "app".to_owned())
+    );
+    for (name, args, about) in BUILTIN_COMMANDS.iter() {
+        println!(
+            "  {:<32} {}",
+            format!("{} {}", name, args).trim_end(),
+            about
+        );
+    }
+
+    let mut first = true;
+    for m in module::all::enabled_modules().iter() {
+        for c in m.commands().iter() {
+            if BUILTIN_COMMANDS
+                .iter()
+                .any(|(name, _, _)| *name == c.name())
+            {
+                trace::warn!(
+                    "Command \"{}\" of module \"{}\" ignored, it is a builtin command",
+                    c.name(),
+                    m.single_name()
+                );
+                continue;
+            }
+            if first {
+                println!("\nModule commands:");
+                first = false;
+            }
+            println!("  {:<32} {} ({})", c.name(), c.about(), m.single_name());
+        }
+    }
+}
+
+// MIGRATE *****************************************************************************************
+
+#[cfg(feature = "database")]
+fn migrate_command(args: &[String]) -> Result<(), Error> {
+    let steps = match args.get(1) {
+        Some(steps) => Some(steps.parse::<u32>().map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid number of steps \"{}\"", steps),
+            )
+        })?),
+        None => None,
+    };
+    let result = match args.first().map(String::as_str) {
+        Some("up") => module::all::migrate_up(steps),
+        // Por precaución, sin indicar pasos sólo se deshace la última migración.
+        Some("down") => module::all::migrate_down(Some(steps.unwrap_or(1))),
+        Some("fresh") => module::all::migrate_fresh(),
+        Some("status") => module::all::migrations_status().map(|status| {
+            for (migration, status) in status.iter() {
+                println!("{:<60} {}", migration, status);
+            }
+        }),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Usage: migrate up [steps] | down [steps] | status | fresh",
+            ))
+        }
+    };
+    result.map_err(|e| Error::other(format!("Migration failed ({})", e)))
+}
+
+#[cfg(not(feature = "database"))]
+fn migrate_command(_args: &[String]) -> Result<(), Error> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "Migrations require PageTop built with the \"database\" feature",
+    ))
+}
+
+// CONFIG ******************************************************************************************
+
+fn config_command(args: &[String]) -> Result<(), Error> {
+    if args.first().map(String::as_str) != Some("show") {
+        return Err(Error::new(ErrorKind::InvalidInput, "Usage: config show"));
+    }
+
+    // Ajustes de los archivos de configuración, incluidos los de secciones propias de los módulos.
+    let mut settings: toml::Table = config::CONFIG
+        .clone()
+        .try_into()
+        .map_err(|e| Error::new(ErrorKind::InvalidData, format!("{}", e)))?;
+
+    // Ajustes globales de PageTop, con los valores predefinidos de los no asignados.
+    let global = toml::Table::try_from(&*config::SETTINGS)
+        .map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))?;
+    for (section, values) in global.into_iter() {
+        if let (Some(toml::Value::Table(table)), toml::Value::Table(values)) =
+            (settings.get_mut(&section), &values)
+        {
+            table.extend(values.clone());
+            continue;
+        }
+        settings.insert(section, values);
+    }
+
+    redact_secrets(&mut settings);
+    print!(
+        "{}",
+        toml::to_string(&settings).map_err(|e| Error::other(e.to_string()))?
+    );
+    Ok(())
+}
+
+fn redact_secrets(table: &mut toml::Table) {
+    for (key, value) in table.iter_mut() {
+        match value {
+            toml::Value::Table(table) => redact_secrets(table),
+            toml::Value::String(s) if s.is_empty() => {}
+            _ if is_secret(key) => *value = toml::Value::String(REDACTED.to_owned()),
+            _ => {}
+        }
+    }
+}
+
+// Los ajustes con claves, contraseñas o tokens se consideran secretos, pero no las rutas a los
+// archivos que los contienen, como "session_key_file" o "tls_key_path".
+fn is_secret(key: &str) -> bool {
+    let key = key.to_lowercase();
+    if key.ends_with("_file") || key.ends_with("_path") {
+        return false;
+    }
+    key.split(['_', '-', '.']).any(|word| {
+        matches!(
+            word,
+            "key" | "pass" | "passwd" | "password" | "secret" | "token"
+        )
+    })
+}
+
+// CRON ********************************************************************************************
+
+fn cron_command(args: &[String]) -> Result<(), Error> {
+    let tasks = module::all::scheduled_tasks();
+    match (args.first().map(String::as_str), args.get(1)) {
+        (Some("list"), None) => {
+            for (module, task) in tasks.iter() {
+                println!("  {:<32} {:<24} {}", task.name(), module, task.schedule());
+            }
+            Ok(())
+        }
+        (Some("run"), name) => {
+            let selected = tasks
+                .iter()
+                .filter(|(_, task)| name.is_none_or(|name| task.name() == name))
+                .collect::<Vec<_>>();
+            if let (Some(name), true) = (name, selected.is_empty()) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unknown scheduled task \"{}\"", name),
+                ));
+            }
+            let mut failed = 0;
+            for (module, task) in selected.into_iter() {
+                let run = module::all::run_scheduled_task(module, task);
+                match run.outcome() {
+                    Ok(()) => println!("  {:<32} ok ({:?})", task.name(), run.duration()),
+                    Err(e) => {
+                        failed += 1;
+                        println!("  {:<32} failed ({:?}): {}", task.name(), run.duration(), e);
+                    }
+                }
+            }
+            match failed {
+                0 => Ok(()),
+                _ => Err(Error::other(format!("{} scheduled tasks failed", failed))),
+            }
+        }
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Usage: cron run [task] | list",
+        )),
+    }
+}
+
+// MAINTENANCE *************************************************************************************
+
+fn maintenance_command(args: &[String]) -> Result<(), Error> {
+    match args.first().map(String::as_str) {
+        Some("on") => maintenance::switch(true)?,
+        Some("off") => maintenance::switch(false)?,
+        Some("status") => {}
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Usage: maintenance on | off | status",
+            ))
+        }
+    }
+    println!(
+        "Maintenance mode is {}",
+        if is_in_maintenance() { "on" } else { "off" }
+    );
+    if config::SETTINGS.server.maintenance_mode {
+        println!("(forced by \"maintenance_mode\" in [server] settings)");
+    }
+    Ok(())
+}
+
+// MODULE ******************************************************************************************
+
+#[cfg(feature = "database")]
+fn module_command(args: &[String]) -> Result<(), Error> {
+    let name = match args.get(1) {
+        Some(name) => name.as_str(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Usage: module install | disable | uninstall <name>",
+            ))
+        }
+    };
+    let result = match args.first().map(String::as_str) {
+        Some("install") => module::all::install_module(name),
+        Some("disable") => module::all::disable_module(name),
+        Some("uninstall") => module::all::uninstall_module(name),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Usage: module install | disable | uninstall <name>",
+            ))
+        }
+    };
+    result.map_err(|e| Error::other(format!("Module status not changed ({})", e)))?;
+    if let Some(status) = module::all::module_status(name) {
+        println!(
+            "Module \"{}\" is {}. Restart the application to apply it.",
+            name, status
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "database"))]
+fn module_command(_args: &[String]) -> Result<(), Error> {
+    Err(Error::new(
+        ErrorKind::Unsupported,
+        "Module status requires PageTop built with the \"database\" feature",
+    ))
+}
+
+// MODULES *****************************************************************************************
+
+fn modules_command() -> Result<(), Error> {
+    println!("Enabled modules (in initialization order):");
+    for m in module::all::enabled_modules().iter() {
+        print_module(*m);
+    }
+
+    let inactive = module::all::inactive_modules();
+    if !inactive.is_empty() {
+        println!("\nInactive modules:");
+        for m in inactive.iter() {
+            print_module(*m);
+        }
+    }
+
+    let dropped = module::all::dropped_modules();
+    if !dropped.is_empty() {
+        println!("\nDropped modules:");
+        for m in dropped.iter() {
+            print_module(*m);
+        }
+    }
+    Ok(())
+}
+
+fn print_module(module: ModuleRef) {
+    let kind = if module.theme().is_some() {
+        " (theme)"
+    } else {
+        ""
+    };
+    let version = module.version().unwrap_or("-");
+    let dependencies = module
+        .dependencies()
+        .iter()
+        .map(|d| d.single_name())
+        .collect::<Vec<_>>();
+    if dependencies.is_empty() {
+        println!("  {} {}{}", module.single_name(), version, kind);
+    } else {
+        println!(
+            "  {} {}{} -> {}",
+            module.single_name(),
+            version,
+            kind,
+            dependencies.join(", ")
+        );
+    }
+}
+
+// ACTIONS *****************************************************************************************
+
+fn actions_command() -> Result<(), Error> {
+    for a in action::registered_actions().iter() {
+        let referer = match (a.referer_name(), a.referer_id()) {
+            (Some(name), Some(id)) => format!("{} #{}", name, id),
+            (Some(name), None) => name,
+            (None, Some(id)) => format!("#{}", id),
+            (None, None) => String::new(),
+        };
+        println!(
+            "  {:<28} {:<32} {:<32} {:>4}",
+            a.module(),
+            a.name(),
+            referer,
+            a.weight()
+        );
+    }
+    Ok(())
+}
+
+// ROUTES ******************************************************************************************
+
+fn routes_command() -> Result<(), Error> {
+    // Configura los servicios como lo hace el servidor web para recoger las rutas declaradas.
+    let _ = service_app();
+
+    let mut routes = service::routes();
+    routes.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    for (method, path) in routes.iter() {
+        println!("  {:<8} {}", method, path);
+    }
+    println!(
+        "\nRoutes added without service_for_route! or service_for_static_files! are not listed."
+    );
+    Ok(())
+}
+
+// THEMES ******************************************************************************************
+
+fn themes_command() -> Result<(), Error> {
+    let default = THEME.handle();
+    for t in THEMES.read().unwrap().iter() {
+        println!(
+            "  {} {}",
+            if t.handle() == default { "*" } else { " " },
+            t.single_name()
+        );
+    }
+    println!("\n* Default theme.");
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::Handle;
+
+#[cfg(feature = "database")]
+use crate::db::DbErr;
+
+use std::fmt;
+use std::io;
+
+/// Errores que impiden preparar la aplicación con [`Application::prepare()`].
+///
+/// Las cadenas de dependencias (`chain`) recogen los nombres de los módulos desde la aplicación
+/// hasta el módulo que provoca el error.
+///
+/// [`Application::prepare()`]: crate::app::Application::prepare
+#[derive(Debug)]
+pub enum PrepareError {
+    /// Se intenta habilitar un módulo que otro módulo ha descartado.
+    DroppedDependency {
+        module: &'static str,
+        chain: Vec<&'static str>,
+    },
+    /// Un módulo depende, directa o indirectamente, de sí mismo.
+    DependencyCycle { chain: Vec<&'static str> },
+    /// Dos tipos distintos (módulos, temas, componentes, acciones u otros elementos) comparten el
+    /// mismo identificador.
+    HandleCollision {
+        handle: Handle,
+        element: &'static str,
+        other: &'static str,
+    },
+    /// La versión de un módulo no es válida, o su requisito de versión de PageTop no es válido.
+    InvalidVersion {
+        module: &'static str,
+        version: &'static str,
+    },
+    /// Un módulo no es compatible con la versión de PageTop en uso.
+    IncompatibleModule {
+        module: &'static str,
+        requires: &'static str,
+        pagetop: &'static str,
+    },
+    /// Ajustes de configuración no válidos.
+    Config(io::Error),
+    /// No se puede conectar con la base de datos.
+    #[cfg(feature = "database")]
+    Database(DbErr),
+}
+
+impl fmt::Display for PrepareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrepareError::DroppedDependency { module, chain } => write!(
+                f,
+                "Trying to enable \"{}\" module which is dropped ({})",
+                module,
+                chain.join(" -> ")
+            ),
+            PrepareError::DependencyCycle { chain } => {
+                write!(f, "Module dependency cycle ({})", chain.join(" -> "))
+            }
+            PrepareError::HandleCollision {
+                handle,
+                element,
+                other,
+            } => write!(
+                f,
+                "Elements \"{}\" and \"{}\" share the same handle {}",
+                other, element, handle
+            ),
+            PrepareError::InvalidVersion { module, version } => write!(
+                f,
+                "Module \"{}\" has an invalid version or requirement \"{}\"",
+                module, version
+            ),
+            PrepareError::IncompatibleModule {
+                module,
+                requires,
+                pagetop,
+            } => write!(
+                f,
+                "Module \"{}\" requires PageTop {} but this is PageTop {}",
+                module, requires, pagetop
+            ),
+            PrepareError::Config(e) => write!(f, "Invalid configuration ({})", e),
+            #[cfg(feature = "database")]
+            PrepareError::Database(e) => write!(f, "Failed to connect to database ({})", e),
+        }
+    }
+}
+
+impl std::error::Error for PrepareError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PrepareError::Config(e) => Some(e),
+            #[cfg(feature = "database")]
+            PrepareError::Database(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PrepareError {
+    fn from(e: io::Error) -> Self {
+        PrepareError::Config(e)
+    }
+}
+
+#[cfg(feature = "database")]
+impl From<DbErr> for PrepareError {
+    fn from(e: DbErr) -> Self {
+        PrepareError::Database(e)
+    }
+}
+
+impl From<PrepareError> for io::Error {
+    fn from(e: PrepareError) -> Self {
+        match e {
+            PrepareError::Config(e) => e,
+            e => io::Error::other(e),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::core::module::{self, Health, HealthStatus};
+use crate::service::http::{header::ContentType, StatusCode};
+use crate::{config, service};
+
+#[cfg(feature = "database")]
+use crate::db;
+
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct HealthResponse {
+    status: HealthStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checks: Vec<HealthCheckResponse>,
+}
+
+#[derive(Serialize)]
+struct HealthCheckResponse {
+    name: &'static str,
+    #[serde(flatten)]
+    health: Health,
+}
+
+pub(crate) fn configure_services(scfg: &mut service::web::ServiceConfig) {
+    let health_path = config::SETTINGS.server.health_path.trim();
+    if !health_path.is_empty() {
+        service::add_route("get", health_path);
+        scfg.route(health_path, service::web::get().to(liveness));
+    }
+    let ready_path = config::SETTINGS.server.ready_path.trim();
+    if !ready_path.is_empty() {
+        service::add_route("get", ready_path);
+        scfg.route(ready_path, service::web::get().to(readiness));
+    }
+}
+
+// Si la aplicación responde, está viva.
+async fn liveness() -> service::HttpResponse {
+    health_response(Vec::new())
+}
+
+// Está lista si la base de datos responde, no quedan migraciones pendientes y todos los módulos
+// habilitados superan sus comprobaciones.
+async fn readiness() -> service::HttpResponse {
+    let mut checks = Vec::new();
+
+    #[cfg(feature = "database")]
+    if let Some(dbconn) = db::DBCONN.get() {
+        checks.push(HealthCheckResponse {
+            name: "database",
+            health: match dbconn_ping(dbconn).await {
+                Ok(_) => Health::up(""),
+                Err(e) => Health::down(e.to_string()),
+            },
+        });
+        checks.push(HealthCheckResponse {
+            name: "migrations",
+            health: match module::all::pending_migrations().await {
+                Ok(0) => Health::up(""),
+                Ok(pending) => Health::down(format!("{} pending migrations", pending)),
+                Err(e) => Health::down(e.to_string()),
+            },
+        });
+    }
+
+    for (name, health) in module::all::health_checks().await.into_iter() {
+        checks.push(HealthCheckResponse { name, health });
+    }
+
+    health_response(checks)
+}
+
+#[cfg(feature = "database")]
+async fn dbconn_ping(dbconn: &db::DbConn) -> Result<(), db::DbErr> {
+    use sea_orm::{ConnectionTrait, Statement};
+
+    let backend = dbconn.get_database_backend();
+    dbconn
+        .query_one(Statement::from_string(backend, "SELECT 1".to_owned()))
+        .await
+        .map(|_| ())
+}
+
+fn health_response(checks: Vec<HealthCheckResponse>) -> service::HttpResponse {
+    let status = if checks.iter().all(|c| c.health.status() == HealthStatus::Up) {
+        HealthStatus::Up
+    } else {
+        HealthStatus::Down
+    };
+    service::HttpResponse::build(match status {
+        HealthStatus::Up => StatusCode::OK,
+        HealthStatus::Down => StatusCode::SERVICE_UNAVAILABLE,
+    })
+    .insert_header(ContentType::json())
+    .body(serde_json::to_string(&HealthResponse { status, checks }).unwrap_or_default())
+}
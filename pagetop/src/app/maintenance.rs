@@ -0,0 +1,113 @@
+use crate::response::fatal_error::FatalError;
+use crate::service;
+use crate::{config, trace, LazyStatic};
+
+use actix_session::SessionExt;
+
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Clave de la sesión que permite navegar por el sitio en modo mantenimiento.
+const MAINTENANCE_BYPASS: &str = "maintenance_bypass";
+
+// Tiempo durante el que se reutiliza la comprobación del archivo indicador.
+const FLAG_FILE_TTL: Duration = Duration::from_secs(2);
+
+// Última comprobación del archivo indicador, para no consultar el sistema de archivos en cada
+// petición.
+static FLAG_FILE_CHECK: LazyStatic<Mutex<Option<(Instant, bool)>>> =
+    LazyStatic::new(|| Mutex::new(None));
+
+/// Permite a la sesión del usuario acceder al sitio aunque esté en modo mantenimiento.
+///
+/// Los módulos deben usarla sólo para usuarios con privilegios, por ejemplo, al identificar a un
+/// administrador.
+pub fn allow_in_maintenance(session: &service::Session) {
+    if let Err(e) = session.insert(MAINTENANCE_BYPASS, true) {
+        trace::error!("Failed to allow the session in maintenance mode ({})", e);
+    }
+}
+
+/// Comprueba si el sitio está en modo mantenimiento, por configuración o porque existe el archivo
+/// `SETTINGS.server.maintenance_flag_file`. La existencia del archivo se comprueba como mucho cada
+/// dos segundos.
+pub fn is_in_maintenance() -> bool {
+    config::SETTINGS.server.maintenance_mode || flag_file_exists()
+}
+
+/// Responde con la página del error 503 si el sitio está en modo mantenimiento, salvo para las rutas
+/// permitidas y las sesiones autorizadas.
+pub(crate) fn check(request: service::Request) -> Result<service::Request, Box<service::Response>> {
+    if !is_in_maintenance() || is_allowed(request.path()) || has_bypass(&request) {
+        return Ok(request);
+    }
+    let error = FatalError::ServiceUnavailable(request.request().clone());
+    Err(Box::new(request.error_response(error)))
+}
+
+fn is_allowed(path: &str) -> bool {
+    config::SETTINGS
+        .server
+        .maintenance_allowed_paths
+        .split(',')
+        .chain([
+            config::SETTINGS.server.health_path.as_str(),
+            config::SETTINGS.server.ready_path.as_str(),
+        ])
+        .map(str::trim)
+        .filter(|allowed| !allowed.is_empty())
+        .any(|allowed| service::path_starts_with(path, allowed))
+        || service::is_static_files(path)
+}
+
+fn has_bypass(request: &service::Request) -> bool {
+    matches!(
+        request.get_session().get::<bool>(MAINTENANCE_BYPASS),
+        Ok(Some(true))
+    )
+}
+
+// MAINTENANCE FLAG FILE ***************************************************************************
+
+fn flag_file() -> Option<&'static Path> {
+    match config::SETTINGS.server.maintenance_flag_file.trim() {
+        "" => None,
+        file => Some(Path::new(file)),
+    }
+}
+
+fn flag_file_exists() -> bool {
+    let file = match flag_file() {
+        Some(file) => file,
+        None => return false,
+    };
+    let mut check = FLAG_FILE_CHECK.lock().unwrap();
+    match *check {
+        Some((checked, exists)) if checked.elapsed() < FLAG_FILE_TTL => exists,
+        _ => {
+            let exists = file.exists();
+            *check = Some((Instant::now(), exists));
+            exists
+        }
+    }
+}
+
+/// Activa o desactiva el modo mantenimiento creando o eliminando el archivo indicador.
+pub(crate) fn switch(on: bool) -> Result<(), Error> {
+    let file = flag_file().ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            "Set \"maintenance_flag_file\" in [server] settings to switch maintenance mode",
+        )
+    })?;
+    match on {
+        true => fs::write(file, b"")?,
+        false if file.exists() => fs::remove_file(file)?,
+        false => {}
+    }
+    *FLAG_FILE_CHECK.lock().unwrap() = Some((Instant::now(), on));
+    Ok(())
+}
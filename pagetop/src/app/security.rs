@@ -0,0 +1,111 @@
+use crate::config::{self, SecurityRoute};
+use crate::core::component::ContentSources;
+use crate::service::http::header::{self, HeaderValue};
+use crate::service::{self, HttpMessage};
+
+/// Añade a la respuesta las cabeceras de seguridad configuradas en `SETTINGS.security` que no haya
+/// establecido ya el propio servicio.
+pub(crate) fn add_headers(response: &mut service::Response) {
+    let request = response.request();
+    let route = route_overrides(request.path());
+    let secure = request.connection_info().scheme() == "https";
+    let sources = request
+        .extensions()
+        .get::<ContentSources>()
+        .cloned()
+        .unwrap_or_default();
+
+    let csp = content_security_policy(
+        setting(
+            route.and_then(|r| r.content_security_policy.as_deref()),
+            &config::SETTINGS.security.content_security_policy,
+        ),
+        &sources,
+    );
+    let hsts = match secure {
+        true => setting(
+            route.and_then(|r| r.strict_transport_security.as_deref()),
+            &config::SETTINGS.security.strict_transport_security,
+        ),
+        false => "",
+    };
+    let frame_options = setting(
+        route.and_then(|r| r.x_frame_options.as_deref()),
+        &config::SETTINGS.security.x_frame_options,
+    );
+    let referrer_policy = setting(
+        route.and_then(|r| r.referrer_policy.as_deref()),
+        &config::SETTINGS.security.referrer_policy,
+    );
+    let permissions_policy = setting(
+        route.and_then(|r| r.permissions_policy.as_deref()),
+        &config::SETTINGS.security.permissions_policy,
+    );
+
+    let headers = response.headers_mut();
+    for (name, value) in [
+        (header::CONTENT_SECURITY_POLICY, csp.as_str()),
+        (header::STRICT_TRANSPORT_SECURITY, hsts),
+        (header::X_FRAME_OPTIONS, frame_options),
+        (header::REFERRER_POLICY, referrer_policy),
+        (header::PERMISSIONS_POLICY, permissions_policy),
+    ] {
+        if value.is_empty() || headers.contains_key(&name) {
+            continue;
+        }
+        if let Ok(value) = HeaderValue::from_str(value) {
+            headers.insert(name, value);
+        }
+    }
+}
+
+// Sustituciones para la ruta con el prefijo más largo que coincida.
+fn route_overrides(path: &str) -> Option<&'static SecurityRoute> {
+    config::SETTINGS
+        .security
+        .routes
+        .iter()
+        .filter(|(prefix, _)| service::path_starts_with(path, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, route)| route)
+}
+
+fn setting<'a>(route: Option<&'a str>, default: &'a str) -> &'a str {
+    route.unwrap_or(default).trim()
+}
+
+// Añade las fuentes de los scripts y estilos de la página a las directivas script-src y style-src
+// de la política, creándolas a partir de 'self' si no existen.
+fn content_security_policy(policy: &str, sources: &ContentSources) -> String {
+    if policy.is_empty() {
+        return String::new();
+    }
+    let mut directives: Vec<String> = policy
+        .split(';')
+        .map(|d| d.trim().to_owned())
+        .filter(|d| !d.is_empty())
+        .collect();
+    for (name, sources) in [
+        ("script-src", &sources.script_src),
+        ("style-src", &sources.style_src),
+    ] {
+        let index = match directives
+            .iter()
+            .position(|d| d.split_whitespace().next() == Some(name))
+        {
+            Some(index) => index,
+            None => {
+                directives.push(format!("{} 'self'", name));
+                directives.len() - 1
+            }
+        };
+        let directive = &mut directives[index];
+        for source in sources.iter() {
+            if !directive.split_whitespace().any(|s| s == source) {
+                directive.push(' ');
+                directive.push_str(source);
+            }
+        }
+    }
+    directives.join("; ")
+}
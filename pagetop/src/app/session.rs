@@ -0,0 +1,444 @@
+mod file;
+mod memory;
+
+#[cfg(feature = "database")]
+mod database;
+
+use crate::service::cookie::{time, Cookie, CookieJar, Key, SameSite};
+use crate::service::http::header::{self, HeaderValue};
+use crate::{config, service, trace};
+
+use actix_session::storage::{
+    CookieSessionStore, LoadError, SaveError, SessionKey, SessionStore, UpdateError,
+};
+
+use rand::distributions::Alphanumeric;
+use rand::{rngs::OsRng, Rng};
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+type SessionState = HashMap<String, String>;
+
+/// Nombre de la cookie de sesión.
+pub(crate) const SESSION_COOKIE_NAME: &str = "id";
+
+/// Longitud mínima en bytes de la clave maestra usada para cifrar las cookies de sesión.
+const SESSION_KEY_MIN_LENGTH: usize = 32;
+
+/// Modos de ejecución en los que se permite generar una clave temporal si no se ha configurado.
+const DEV_RUN_MODES: [&str; 3] = ["dev", "devel", "development"];
+
+#[derive(Clone)]
+pub(crate) struct SessionKeys {
+    current: Key,
+    old: Option<Key>,
+}
+
+impl SessionKeys {
+    pub fn from_settings() -> Result<Self, Error> {
+        let current = match master_key(
+            "session_key",
+            &config::SETTINGS.server.session_key,
+            &config::SETTINGS.server.session_key_file,
+        )? {
+            Some(key) => key,
+            None => {
+                let run_mode = config::SETTINGS.app.run_mode.to_lowercase();
+                if !DEV_RUN_MODES.contains(&run_mode.as_str()) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "No session key for run mode \"{}\". {} {}",
+                            config::SETTINGS.app.run_mode,
+                            "Set \"session_key\" or \"session_key_file\" in [server] settings,",
+                            "or use PAGETOP_RUN_MODE=dev for a temporary key"
+                        ),
+                    ));
+                }
+                trace::warn!(
+                    "No session key for run mode \"{}\", using a temporary one. {}",
+                    config::SETTINGS.app.run_mode,
+                    "Sessions will be lost on restart"
+                );
+                Key::generate()
+            }
+        };
+        let old = master_key(
+            "session_key_old",
+            &config::SETTINGS.server.session_key_old,
+            &config::SETTINGS.server.session_key_old_file,
+        )?;
+        Ok(SessionKeys { current, old })
+    }
+
+    pub fn current(&self) -> &Key {
+        &self.current
+    }
+
+    /// Vuelve a cifrar con la clave actual la cookie de sesión de la petición si sólo puede
+    /// descifrarse con la clave anterior. Así se mantienen las sesiones abiertas durante la
+    /// rotación de claves. Devuelve la cookie cifrada de nuevo para enviarla en la respuesta.
+    pub fn rotate_cookie(&self, request: &mut service::Request) -> Option<Cookie<'static>> {
+        let old = self.old.as_ref()?;
+
+        let mut rotated = None;
+        let mut cookies: Vec<String> = Vec::new();
+        for value in request.headers().get_all(header::COOKIE) {
+            for pair in value.to_str().unwrap_or_default().split(';') {
+                let pair = pair.trim();
+                if pair.is_empty() {
+                    continue;
+                }
+                if let Ok(cookie) = Cookie::parse_encoded(pair.to_owned()) {
+                    if cookie.name() == SESSION_COOKIE_NAME {
+                        if let Some(fresh) = self.reencrypt(cookie, old) {
+                            cookies.push(fresh.encoded().stripped().to_string());
+                            rotated = Some(fresh);
+                            continue;
+                        }
+                    }
+                }
+                cookies.push(pair.to_owned());
+            }
+        }
+
+        let mut fresh = rotated?;
+        if let Ok(value) = HeaderValue::from_str(&cookies.join("; ")) {
+            request.headers_mut().insert(header::COOKIE, value);
+        }
+
+        // Mismos atributos que la cookie del middleware de sesión.
+        fresh.set_path("/");
+        fresh.set_secure(true);
+        fresh.set_http_only(true);
+        fresh.set_same_site(SameSite::Lax);
+        if config::SETTINGS.server.session_lifetime > 0 {
+            fresh.set_max_age(time::Duration::seconds(
+                config::SETTINGS.server.session_lifetime,
+            ));
+        }
+        Some(fresh)
+    }
+
+    fn reencrypt(&self, cookie: Cookie<'static>, old: &Key) -> Option<Cookie<'static>> {
+        let jar = CookieJar::new();
+        if jar.private(&self.current).decrypt(cookie.clone()).is_some() {
+            return None;
+        }
+        let plain = jar.private(old).decrypt(cookie)?;
+
+        let mut jar = CookieJar::new();
+        jar.private_mut(&self.current).add(plain);
+        jar.get(SESSION_COOKIE_NAME).cloned()
+    }
+}
+
+fn master_key(setting: &str, value: &str, file: &str) -> Result<Option<Key>, Error> {
+    let value = value.trim();
+    let file = file.trim();
+
+    let master = match (value.is_empty(), file.is_empty()) {
+        (true, true) => return Ok(None),
+        (false, true) => value.to_owned(),
+        (true, false) => fs::read_to_string(file)
+            .map_err(|e| {
+                Error::new(
+                    e.kind(),
+                    format!("Failed to read \"{}_file\" \"{}\" ({})", setting, file, e),
+                )
+            })?
+            .trim()
+            .to_owned(),
+        (false, false) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Use \"{}\" or \"{}_file\" in [server] settings, not both",
+                    setting, setting
+                ),
+            ))
+        }
+    };
+
+    if master.len() < SESSION_KEY_MIN_LENGTH {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "Value for \"{}\" must be at least {} bytes long",
+                setting, SESSION_KEY_MIN_LENGTH
+            ),
+        ));
+    }
+    Ok(Some(Key::derive_from(master.as_bytes())))
+}
+
+// SESSION STORES **********************************************************************************
+
+/// Almacén para los datos de sesión según `SETTINGS.server.session_store`.
+#[derive(Clone)]
+pub(crate) enum SessionBackend {
+    Cookie,
+    Memory,
+    File(PathBuf),
+    #[cfg(feature = "database")]
+    Database,
+}
+
+impl SessionBackend {
+    pub fn from_settings() -> Result<Self, Error> {
+        let session_store = config::SETTINGS.server.session_store.to_lowercase();
+        match session_store.as_str() {
+            "cookie" => Ok(SessionBackend::Cookie),
+            "memory" => Ok(SessionBackend::Memory),
+            "file" => {
+                let path = PathBuf::from(&config::SETTINGS.server.session_store_path);
+                fs::create_dir_all(&path).map_err(|e| {
+                    Error::new(
+                        e.kind(),
+                        format!(
+                            "Failed to create the session store directory \"{}\" ({})",
+                            path.display(),
+                            e
+                        ),
+                    )
+                })?;
+                Ok(SessionBackend::File(path))
+            }
+            #[cfg(feature = "database")]
+            "database" => {
+                database::install().map_err(|e| {
+                    Error::other(format!(
+                        "Failed to install the database session store ({})",
+                        e
+                    ))
+                })?;
+                Ok(SessionBackend::Database)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Session store \"{}\" not valid. Check the settings file",
+                    config::SETTINGS.server.session_store
+                ),
+            )),
+        }
+    }
+
+    /// Elimina periódicamente las sesiones caducadas de los almacenes en el servidor.
+    pub fn spawn_sweeper(&self) {
+        let interval = config::SETTINGS.server.session_sweep_interval;
+        if interval == 0 || matches!(self, SessionBackend::Cookie) {
+            return;
+        }
+        let backend = self.clone();
+        service::rt::spawn(async move {
+            let mut timer = service::rt::time::interval(Duration::from_secs(interval));
+            loop {
+                timer.tick().await;
+                backend.sweep().await;
+            }
+        });
+    }
+
+    async fn sweep(&self) {
+        let swept = match self {
+            SessionBackend::Cookie => Ok(()),
+            SessionBackend::Memory => {
+                trace::debug!("Swept {} expired sessions from memory", memory::sweep());
+                Ok(())
+            }
+            SessionBackend::File(dir) => file::sweep(dir).map(|swept| {
+                trace::debug!("Swept {} expired session files", swept);
+            }),
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::sweep().await,
+        };
+        if let Err(e) = swept {
+            trace::error!("Failed to sweep expired sessions ({})", e);
+        }
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl SessionStore for SessionBackend {
+    async fn load(&self, session_key: &SessionKey) -> Result<Option<SessionState>, LoadError> {
+        let key = session_key.as_ref();
+        match self {
+            SessionBackend::Cookie => CookieSessionStore::default().load(session_key).await,
+            SessionBackend::Memory => Ok(memory::load(key)),
+            SessionBackend::File(dir) => file::load(dir, key).map_err(LoadError::Other),
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::load(key).await.map_err(LoadError::Other),
+        }
+    }
+
+    async fn save(
+        &self,
+        session_state: SessionState,
+        ttl: &service::cookie::time::Duration,
+    ) -> Result<SessionKey, SaveError> {
+        if let SessionBackend::Cookie = self {
+            return CookieSessionStore::default().save(session_state, ttl).await;
+        }
+        let session_key = generate_session_key();
+        let key = session_key.as_ref();
+        let ttl = ttl.whole_seconds();
+        match self {
+            SessionBackend::Cookie => {}
+            SessionBackend::Memory => memory::save(key, session_state, ttl),
+            SessionBackend::File(dir) => {
+                file::save(dir, key, session_state, ttl).map_err(SaveError::Other)?
+            }
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::save(key, session_state, ttl)
+                .await
+                .map_err(SaveError::Other)?,
+        }
+        Ok(session_key)
+    }
+
+    async fn update(
+        &self,
+        session_key: SessionKey,
+        session_state: SessionState,
+        ttl: &service::cookie::time::Duration,
+    ) -> Result<SessionKey, UpdateError> {
+        let key = session_key.as_ref();
+        let seconds = ttl.whole_seconds();
+        match self {
+            SessionBackend::Cookie => {
+                return CookieSessionStore::default()
+                    .update(session_key, session_state, ttl)
+                    .await
+            }
+            SessionBackend::Memory => memory::save(key, session_state, seconds),
+            SessionBackend::File(dir) => {
+                file::save(dir, key, session_state, seconds).map_err(UpdateError::Other)?
+            }
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::update(key, session_state, seconds)
+                .await
+                .map_err(UpdateError::Other)?,
+        }
+        Ok(session_key)
+    }
+
+    async fn update_ttl(
+        &self,
+        session_key: &SessionKey,
+        ttl: &service::cookie::time::Duration,
+    ) -> Result<(), anyhow::Error> {
+        let key = session_key.as_ref();
+        let ttl = ttl.whole_seconds();
+        match self {
+            SessionBackend::Cookie => Ok(()),
+            SessionBackend::Memory => {
+                memory::update_ttl(key, ttl);
+                Ok(())
+            }
+            SessionBackend::File(dir) => file::update_ttl(dir, key, ttl),
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::update_ttl(key, ttl).await,
+        }
+    }
+
+    async fn delete(&self, session_key: &SessionKey) -> Result<(), anyhow::Error> {
+        let key = session_key.as_ref();
+        match self {
+            SessionBackend::Cookie => Ok(()),
+            SessionBackend::Memory => {
+                memory::delete(key);
+                Ok(())
+            }
+            SessionBackend::File(dir) => file::delete(dir, key),
+            #[cfg(feature = "database")]
+            SessionBackend::Database => database::delete(key).await,
+        }
+    }
+}
+
+// Genera claves de sesión de 64 caracteres alfanuméricos siguiendo las recomendaciones de OWASP.
+fn generate_session_key() -> SessionKey {
+    let key: String = (0..64)
+        .map(|_| OsRng.sample(Alphanumeric) as char)
+        .collect();
+    SessionKey::try_from(key).unwrap()
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+fn expires_at(ttl: i64) -> i64 {
+    now() + ttl
+}
+
+fn is_expired(expires: i64) -> bool {
+    expires <= now()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::service::cookie::time::Duration;
+    use crate::service::rt::System;
+
+    // Guarda, actualiza, caduca y elimina una sesión en el almacén `backend`.
+    fn check_store(backend: SessionBackend) {
+        System::new().block_on(async {
+            let ttl = Duration::seconds(60);
+            let mut state = SessionState::new();
+            state.insert("user".to_owned(), "\"alice\"".to_owned());
+
+            let key = backend.save(state.clone(), &ttl).await.unwrap();
+            assert_eq!(backend.load(&key).await.unwrap(), Some(state.clone()));
+
+            state.insert("theme".to_owned(), "\"dark\"".to_owned());
+            let key = backend.update(key, state.clone(), &ttl).await.unwrap();
+            assert_eq!(backend.load(&key).await.unwrap(), Some(state.clone()));
+
+            backend.update_ttl(&key, &Duration::ZERO).await.unwrap();
+            assert_eq!(backend.load(&key).await.unwrap(), None);
+
+            // Las sesiones eliminadas al caducar se vuelven a guardar al actualizarlas.
+            backend.sweep().await;
+            let key = backend.update(key, state.clone(), &ttl).await.unwrap();
+            assert_eq!(backend.load(&key).await.unwrap(), Some(state));
+
+            backend.delete(&key).await.unwrap();
+            assert_eq!(backend.load(&key).await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn test_memory_store() {
+        check_store(SessionBackend::Memory);
+    }
+
+    #[test]
+    fn test_file_store() {
+        let dir = std::env::temp_dir().join(format!("pagetop-sessions-{}", std::process::id()));
+        check_store(SessionBackend::File(dir.clone()));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_database_store() {
+        let path = std::env::temp_dir().join(format!("pagetop-sessions-{}.db", std::process::id()));
+        let uri = format!("sqlite://{}?mode=rwc", path.display());
+        let dbconn = crate::db::run_now(sea_orm::Database::connect(uri)).unwrap();
+        let _ = crate::db::DBCONN.set(dbconn);
+        database::install().unwrap();
+        check_store(SessionBackend::Database);
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,125 @@
+use super::{expires_at, now, SessionState};
+
+use crate::db::*;
+
+use sea_orm::ConnectionTrait;
+
+#[rustfmt::skip]
+#[derive(Iden)]
+enum Sessions {
+    Table,              // sessions: Stores the server-side session states.
+
+    Id,                 // Primary Key: Session key sent in the session cookie.
+    State,              // Session state serialized as JSON.
+    Expires,            // Unix timestamp when the session expires.
+}
+
+pub(crate) fn install() -> Result<(), DbErr> {
+    match DBCONN.get() {
+        Some(dbconn) => {
+            let stmt = Table::create()
+                .table(Sessions::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(Sessions::Id)
+                        .string_len(64)
+                        .not_null()
+                        .primary_key(),
+                )
+                .col(ColumnDef::new(Sessions::State).text().not_null())
+                .col(ColumnDef::new(Sessions::Expires).big_integer().not_null())
+                .to_owned();
+            run_now(dbconn.execute(dbconn.get_database_backend().build(&stmt)))?;
+            Ok(())
+        }
+        None => Err(DbErr::Conn(RuntimeErr::Internal(
+            "Database connection required by the session store".to_owned(),
+        ))),
+    }
+}
+
+pub async fn load(key: &str) -> Result<Option<SessionState>, anyhow::Error> {
+    let rows = query(
+        Query::select()
+            .column(Sessions::State)
+            .from(Sessions::Table)
+            .and_where(Expr::col(Sessions::Id).eq(key))
+            .and_where(Expr::col(Sessions::Expires).gt(now())),
+    )
+    .await?;
+    match rows.first() {
+        Some(row) => {
+            let state: String = row.try_get("", "state")?;
+            Ok(Some(serde_json::from_str(&state)?))
+        }
+        None => Ok(None),
+    }
+}
+
+pub async fn save(key: &str, state: SessionState, ttl: i64) -> Result<(), anyhow::Error> {
+    exec(
+        Query::insert()
+            .into_table(Sessions::Table)
+            .columns([Sessions::Id, Sessions::State, Sessions::Expires])
+            .values_panic([
+                key.into(),
+                serde_json::to_string(&state)?.into(),
+                expires_at(ttl).into(),
+            ]),
+    )
+    .await?;
+    Ok(())
+}
+
+// Si la sesión ya no existe, por ejemplo porque ha caducado y se ha eliminado, se vuelve a guardar
+// para no perder los datos que mantiene el cliente con su clave.
+pub async fn update(key: &str, state: SessionState, ttl: i64) -> Result<(), anyhow::Error> {
+    exec(
+        Query::insert()
+            .into_table(Sessions::Table)
+            .columns([Sessions::Id, Sessions::State, Sessions::Expires])
+            .values_panic([
+                key.into(),
+                serde_json::to_string(&state)?.into(),
+                expires_at(ttl).into(),
+            ])
+            .on_conflict(
+                OnConflict::column(Sessions::Id)
+                    .update_columns([Sessions::State, Sessions::Expires])
+                    .to_owned(),
+            ),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn update_ttl(key: &str, ttl: i64) -> Result<(), anyhow::Error> {
+    exec(
+        Query::update()
+            .table(Sessions::Table)
+            .values([(Sessions::Expires, expires_at(ttl).into())])
+            .and_where(Expr::col(Sessions::Id).eq(key)),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn delete(key: &str) -> Result<(), anyhow::Error> {
+    exec(
+        Query::delete()
+            .from_table(Sessions::Table)
+            .and_where(Expr::col(Sessions::Id).eq(key)),
+    )
+    .await?;
+    Ok(())
+}
+
+pub async fn sweep() -> Result<(), anyhow::Error> {
+    exec(
+        Query::delete()
+            .from_table(Sessions::Table)
+            .and_where(Expr::col(Sessions::Expires).lte(now())),
+    )
+    .await?;
+    Ok(())
+}
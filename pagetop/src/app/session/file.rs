@@ -0,0 +1,94 @@
+use super::{expires_at, is_expired, SessionState};
+
+use serde::{Deserialize, Serialize};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const SESSION_FILE_EXTENSION: &str = "session";
+
+#[derive(Deserialize, Serialize)]
+struct StoredSession {
+    expires: i64,
+    state: SessionState,
+}
+
+fn session_file(dir: &Path, key: &str) -> Result<PathBuf, anyhow::Error> {
+    // Las claves de sesión sólo usan caracteres alfanuméricos, así no pueden salir del directorio.
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(anyhow::anyhow!("Invalid session key"));
+    }
+    Ok(dir.join(key).with_extension(SESSION_FILE_EXTENSION))
+}
+
+fn write(dir: &Path, key: &str, stored: &StoredSession) -> Result<(), anyhow::Error> {
+    fs::create_dir_all(dir)?;
+    fs::write(session_file(dir, key)?, serde_json::to_vec(stored)?)?;
+    Ok(())
+}
+
+fn read(dir: &Path, key: &str) -> Result<Option<StoredSession>, anyhow::Error> {
+    let path = session_file(dir, key)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+}
+
+pub fn load(dir: &Path, key: &str) -> Result<Option<SessionState>, anyhow::Error> {
+    match read(dir, key)? {
+        Some(stored) if !is_expired(stored.expires) => Ok(Some(stored.state)),
+        _ => Ok(None),
+    }
+}
+
+pub fn save(dir: &Path, key: &str, state: SessionState, ttl: i64) -> Result<(), anyhow::Error> {
+    write(
+        dir,
+        key,
+        &StoredSession {
+            expires: expires_at(ttl),
+            state,
+        },
+    )
+}
+
+pub fn update_ttl(dir: &Path, key: &str, ttl: i64) -> Result<(), anyhow::Error> {
+    if let Some(mut stored) = read(dir, key)? {
+        stored.expires = expires_at(ttl);
+        write(dir, key, &stored)?;
+    }
+    Ok(())
+}
+
+pub fn delete(dir: &Path, key: &str) -> Result<(), anyhow::Error> {
+    let path = session_file(dir, key)?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+pub fn sweep(dir: &Path) -> Result<usize, anyhow::Error> {
+    let mut swept = 0;
+    if !dir.exists() {
+        return Ok(swept);
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some(SESSION_FILE_EXTENSION) {
+            continue;
+        }
+        let expired = match fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice::<StoredSession>(&bytes) {
+                Ok(stored) => is_expired(stored.expires),
+                Err(_) => true,
+            },
+            Err(_) => false,
+        };
+        if expired && fs::remove_file(&path).is_ok() {
+            swept += 1;
+        }
+    }
+    Ok(swept)
+}
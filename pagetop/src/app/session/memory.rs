@@ -0,0 +1,41 @@
+use super::{expires_at, is_expired, SessionState};
+
+use crate::LazyStatic;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+// Sesiones guardadas en memoria con su fecha de caducidad.
+static SESSIONS: LazyStatic<RwLock<HashMap<String, (SessionState, i64)>>> =
+    LazyStatic::new(|| RwLock::new(HashMap::new()));
+
+pub fn load(key: &str) -> Option<SessionState> {
+    match SESSIONS.read().unwrap().get(key) {
+        Some((state, expires)) if !is_expired(*expires) => Some(state.clone()),
+        _ => None,
+    }
+}
+
+pub fn save(key: &str, state: SessionState, ttl: i64) {
+    SESSIONS
+        .write()
+        .unwrap()
+        .insert(key.to_owned(), (state, expires_at(ttl)));
+}
+
+pub fn update_ttl(key: &str, ttl: i64) {
+    if let Some((_, expires)) = SESSIONS.write().unwrap().get_mut(key) {
+        *expires = expires_at(ttl);
+    }
+}
+
+pub fn delete(key: &str) {
+    SESSIONS.write().unwrap().remove(key);
+}
+
+pub fn sweep() -> usize {
+    let mut sessions = SESSIONS.write().unwrap();
+    let before = sessions.len();
+    sessions.retain(|_, (_, expires)| !is_expired(*expires));
+    before - sessions.len()
+}
@@ -0,0 +1,115 @@
+use crate::response::redirect::Redirect;
+use crate::{config, service};
+
+use std::io::Error;
+#[cfg(not(feature = "tls"))]
+use std::io::ErrorKind;
+
+#[cfg(feature = "tls")]
+use std::{fs::File, io::BufReader, io::ErrorKind};
+
+/// Indica si se han configurado el certificado o la clave privada para servir HTTPS.
+pub(crate) fn is_enabled() -> bool {
+    !config::SETTINGS.server.tls_cert_path.trim().is_empty()
+        || !config::SETTINGS.server.tls_key_path.trim().is_empty()
+}
+
+#[cfg(feature = "tls")]
+pub(crate) fn server_config() -> Result<Option<rustls::ServerConfig>, Error> {
+    if !is_enabled() {
+        return Ok(None);
+    }
+
+    let cert_path = config::SETTINGS.server.tls_cert_path.trim();
+    let key_path = config::SETTINGS.server.tls_key_path.trim();
+    if cert_path.is_empty() || key_path.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "Both \"tls_cert_path\" and \"tls_key_path\" are required in [server] settings",
+        ));
+    }
+
+    let open = |path: &str| {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|e| Error::new(e.kind(), format!("Failed to open \"{}\" ({})", path, e)))
+    };
+
+    let certs: Vec<rustls::Certificate> = rustls_pemfile::certs(&mut open(cert_path)?)?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    if certs.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("No certificates found in \"{}\"", cert_path),
+        ));
+    }
+
+    let key = rustls_pemfile::read_all(&mut open(key_path)?)?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("No private key found in \"{}\"", key_path),
+            )
+        })?;
+
+    rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map(Some)
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Invalid TLS settings ({})", e),
+            )
+        })
+}
+
+#[cfg(not(feature = "tls"))]
+pub(crate) fn server_config() -> Result<Option<()>, Error> {
+    if is_enabled() {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "HTTPS settings require PageTop to be compiled with the \"tls\" feature",
+        ));
+    }
+    Ok(None)
+}
+
+/// Redirige a HTTPS las peticiones recibidas en el puerto de texto plano.
+pub(crate) fn redirect_to_https(request: &service::Request) -> Option<service::HttpResponse> {
+    if !is_enabled() || request.app_config().secure() {
+        return None;
+    }
+
+    let info = request.connection_info();
+    let host = info.host();
+    // Descarta el puerto del host, teniendo en cuenta las direcciones IPv6 como "[::1]:8088".
+    let host = match host.rfind(':') {
+        Some(index) if !host[index..].contains(']') => &host[..index],
+        _ => host,
+    };
+    let port = match config::SETTINGS.server.bind_port {
+        443 => "".to_owned(),
+        port => format!(":{}", port),
+    };
+    let path = request
+        .uri()
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+
+    Some(Redirect::permanent(&format!(
+        "https://{}{}{}",
+        host, port, path
+    )))
+}
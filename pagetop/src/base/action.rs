@@ -1,3 +1,69 @@
+use crate::core::action::{dispatch_merged_alter_actions, Action, ActionResult, KeyAction};
+use crate::Handle;
+
+use std::ops::ControlFlow;
+
 pub mod component;
 
 pub mod page;
+
+pub mod request;
+
+// Ejecuta en orden de peso las acciones sin filtro y las filtradas por `referer_handle`, por
+// `referer_id` o por ambos. Con el mismo peso se ejecutan antes las menos específicas.
+pub(crate) fn dispatch_filtered_actions<F>(
+    action: Handle,
+    referer_handle: Option<Handle>,
+    referer_id: Option<String>,
+    mut f: F,
+) where
+    F: FnMut(&Action),
+{
+    let _ = dispatch_merged_alter_actions(
+        &filtered_keys(action, referer_handle, referer_id),
+        (),
+        |action, _| {
+            f(action);
+            ActionResult::Continue
+        },
+    );
+}
+
+// Como `dispatch_filtered_actions()`, pero ejecuta las acciones como una cadena de alteraciones que
+// se detiene en cuanto una acción lo indica.
+pub(crate) fn dispatch_filtered_alter_actions<T, F>(
+    action: Handle,
+    referer_handle: Option<Handle>,
+    referer_id: Option<String>,
+    value: T,
+    f: F,
+) -> T
+where
+    F: FnMut(&Action, &T) -> ActionResult<T>,
+{
+    match dispatch_merged_alter_actions(
+        &filtered_keys(action, referer_handle, referer_id),
+        value,
+        f,
+    ) {
+        ControlFlow::Continue(value) | ControlFlow::Break(value) => value,
+    }
+}
+
+fn filtered_keys(
+    action: Handle,
+    referer_handle: Option<Handle>,
+    referer_id: Option<String>,
+) -> Vec<KeyAction> {
+    let mut keys = vec![(action, None, None)];
+    if referer_handle.is_some() {
+        keys.push((action, referer_handle, None));
+    }
+    if referer_id.is_some() {
+        keys.push((action, None, referer_id.clone()));
+        if referer_handle.is_some() {
+            keys.push((action, referer_handle, referer_id));
+        }
+    }
+    keys
+}
@@ -2,8 +2,14 @@ use crate::prelude::*;
 
 pub type FnAction<C> = fn(component: &mut C, cx: &mut Context);
 
+pub type FnActionMarkup<C> =
+    fn(component: &C, cx: &mut Context, markup: &Markup) -> ActionResult<Markup>;
+
 mod before_prepare_component;
 pub use before_prepare_component::*;
 
 mod after_prepare_component;
 pub use after_prepare_component::*;
+
+mod alter_component_markup;
+pub use alter_component_markup::*;
@@ -0,0 +1,71 @@
+use crate::prelude::*;
+
+use crate::base::action::dispatch_filtered_alter_actions;
+
+use super::FnActionMarkup;
+
+/// Altera o sustituye el código HTML renderizado de un componente.
+///
+/// Cada acción recibe el código que deja la anterior y devuelve un [`ActionResult`]:
+/// `ActionResult::Replace(markup)` lo sustituye, y `ActionResult::Stop` o
+/// `ActionResult::Return(markup)` evitan que se ejecuten las siguientes acciones.
+pub struct AlterComponentMarkup<C: ComponentTrait> {
+    f: FnActionMarkup<C>,
+    referer_handle: Option<Handle>,
+    referer_id: OptionId,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_ALTER_COMPONENT_MARKUP for AlterComponentMarkup<ComponentTrait>);
+
+impl<C: ComponentTrait> ActionTrait for AlterComponentMarkup<C> {
+    fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    fn referer_id(&self) -> Option<String> {
+        self.referer_id.get()
+    }
+
+    fn referer_type_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<C>())
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl<C: ComponentTrait> AlterComponentMarkup<C> {
+    pub fn with(f: FnActionMarkup<C>) -> Self {
+        AlterComponentMarkup {
+            f,
+            referer_handle: Some(C::static_handle()),
+            referer_id: OptionId::default(),
+            weight: 0,
+        }
+    }
+
+    pub fn filter_by_referer_id(mut self, id: impl Into<String>) -> Self {
+        self.referer_id.alter_value(id);
+        self
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn dispatch(component: &C, cx: &mut Context, markup: Markup) -> Markup {
+        dispatch_filtered_alter_actions(
+            Self::static_handle(),
+            Some(component.handle()),
+            component.id(),
+            markup,
+            |action, markup| {
+                (action_ref::<AlterComponentMarkup<C>>(&**action).f)(component, cx, markup)
+            },
+        )
+    }
+}
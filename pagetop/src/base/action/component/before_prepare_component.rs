@@ -20,6 +20,10 @@ impl<C: ComponentTrait> ActionTrait for BeforePrepareComponent<C> {
         self.referer_id.get()
     }
 
+    fn referer_type_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<C>())
+    }
+
     fn weight(&self) -> Weight {
         self.weight
     }
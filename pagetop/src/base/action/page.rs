@@ -2,8 +2,26 @@ use crate::prelude::*;
 
 pub type FnActionPage = fn(page: &mut Page);
 
+pub type FnActionRegion = fn(components: &mut ArcComponents, cx: &mut Context);
+
+pub type FnActionFinalMarkup = fn(page: &mut Page, markup: Markup) -> Markup;
+
+pub type FnActionAccess = fn(page: &mut Page, access: &bool) -> ActionResult<bool>;
+
+mod check_page_access;
+pub use check_page_access::*;
+
 mod before_prepare_body;
 pub use before_prepare_body::*;
 
 mod after_prepare_body;
 pub use after_prepare_body::*;
+
+mod before_prepare_head;
+pub use before_prepare_head::*;
+
+mod alter_region_components;
+pub use alter_region_components::*;
+
+mod alter_final_markup;
+pub use alter_final_markup::*;
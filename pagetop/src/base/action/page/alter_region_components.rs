@@ -0,0 +1,66 @@
+use crate::prelude::*;
+
+use crate::base::action::dispatch_filtered_actions;
+
+use super::FnActionRegion;
+
+pub struct AlterRegionComponents {
+    f: FnActionRegion,
+    referer_handle: Option<Handle>,
+    referer_id: OptionId,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_ALTER_REGION_COMPONENTS for AlterRegionComponents);
+
+impl ActionTrait for AlterRegionComponents {
+    fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    fn referer_id(&self) -> Option<String> {
+        self.referer_id.get()
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl AlterRegionComponents {
+    pub fn with(f: FnActionRegion) -> Self {
+        AlterRegionComponents {
+            f,
+            referer_handle: None,
+            referer_id: OptionId::default(),
+            weight: 0,
+        }
+    }
+
+    /// Sólo para las regiones del tema `handle`.
+    pub fn filter_by_referer_handle(mut self, handle: Handle) -> Self {
+        self.referer_handle = Some(handle);
+        self
+    }
+
+    /// Sólo para la región `id`.
+    pub fn filter_by_referer_id(mut self, id: impl Into<String>) -> Self {
+        self.referer_id.alter_value(id);
+        self
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn dispatch(components: &mut ArcComponents, cx: &mut Context, region: &str) {
+        dispatch_filtered_actions(
+            Self::static_handle(),
+            Some(cx.theme().handle()),
+            Some(region.to_owned()),
+            |action| (action_ref::<AlterRegionComponents>(&**action).f)(components, cx),
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use crate::prelude::*;
+
+use crate::base::action::dispatch_filtered_actions;
+
+use super::FnActionPage;
+
+pub struct BeforePrepareHead {
+    f: FnActionPage,
+    referer_handle: Option<Handle>,
+    referer_id: OptionId,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_BEFORE_PREPARE_HEAD for BeforePrepareHead);
+
+impl ActionTrait for BeforePrepareHead {
+    fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    fn referer_id(&self) -> Option<String> {
+        self.referer_id.get()
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl BeforePrepareHead {
+    pub fn with(f: FnActionPage) -> Self {
+        BeforePrepareHead {
+            f,
+            referer_handle: None,
+            referer_id: OptionId::default(),
+            weight: 0,
+        }
+    }
+
+    /// Sólo para las páginas que se renderizan con el tema `handle`.
+    pub fn filter_by_referer_handle(mut self, handle: Handle) -> Self {
+        self.referer_handle = Some(handle);
+        self
+    }
+
+    /// Sólo para las páginas que usan la plantilla `id`.
+    pub fn filter_by_referer_id(mut self, id: impl Into<String>) -> Self {
+        self.referer_id.alter_value(id);
+        self
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn dispatch(page: &mut Page) {
+        dispatch_filtered_actions(
+            Self::static_handle(),
+            Some(page.context().theme().handle()),
+            Some(page.template().to_owned()),
+            |action| (action_ref::<BeforePrepareHead>(&**action).f)(page),
+        );
+    }
+}
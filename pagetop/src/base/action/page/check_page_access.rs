@@ -0,0 +1,73 @@
+use crate::prelude::*;
+
+use crate::base::action::dispatch_filtered_alter_actions;
+
+use super::FnActionAccess;
+
+/// Decide si se puede acceder a la página antes de renderizarla.
+///
+/// Cada acción recibe la decisión de la anterior (inicialmente `true`) y devuelve un
+/// [`ActionResult`]; por ejemplo, `ActionResult::Return(false)` deniega el acceso sin consultar más
+/// acciones. Si el resultado final es `false` la página responde con
+/// [`FatalError::AccessDenied`].
+pub struct CheckPageAccess {
+    f: FnActionAccess,
+    referer_handle: Option<Handle>,
+    referer_id: OptionId,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_CHECK_PAGE_ACCESS for CheckPageAccess);
+
+impl ActionTrait for CheckPageAccess {
+    fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    fn referer_id(&self) -> Option<String> {
+        self.referer_id.get()
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl CheckPageAccess {
+    pub fn with(f: FnActionAccess) -> Self {
+        CheckPageAccess {
+            f,
+            referer_handle: None,
+            referer_id: OptionId::default(),
+            weight: 0,
+        }
+    }
+
+    /// Sólo para las páginas que se renderizan con el tema `handle`.
+    pub fn filter_by_referer_handle(mut self, handle: Handle) -> Self {
+        self.referer_handle = Some(handle);
+        self
+    }
+
+    /// Sólo para las páginas que usan la plantilla `id`.
+    pub fn filter_by_referer_id(mut self, id: impl Into<String>) -> Self {
+        self.referer_id.alter_value(id);
+        self
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn dispatch(page: &mut Page) -> bool {
+        dispatch_filtered_alter_actions(
+            Self::static_handle(),
+            Some(page.context().theme().handle()),
+            Some(page.template().to_owned()),
+            true,
+            |action, access| (action_ref::<CheckPageAccess>(&**action).f)(page, access),
+        )
+    }
+}
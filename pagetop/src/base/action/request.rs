@@ -0,0 +1,13 @@
+use crate::prelude::*;
+
+use crate::service::http::header::HeaderMap;
+
+pub type FnActionRequest = fn(request: &service::HttpRequest);
+
+pub type FnActionResponse = fn(request: &service::HttpRequest, headers: &mut HeaderMap);
+
+mod on_request_start;
+pub use on_request_start::*;
+
+mod on_response_finalize;
+pub use on_response_finalize::*;
@@ -0,0 +1,68 @@
+use crate::prelude::*;
+
+use crate::base::action::dispatch_filtered_actions;
+use crate::service::http::header::HeaderMap;
+
+use super::FnActionResponse;
+
+pub struct OnResponseFinalize {
+    f: FnActionResponse,
+    referer_handle: Option<Handle>,
+    referer_id: OptionId,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_ON_RESPONSE_FINALIZE for OnResponseFinalize);
+
+impl ActionTrait for OnResponseFinalize {
+    fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    fn referer_id(&self) -> Option<String> {
+        self.referer_id.get()
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl OnResponseFinalize {
+    pub fn with(f: FnActionResponse) -> Self {
+        OnResponseFinalize {
+            f,
+            referer_handle: None,
+            referer_id: OptionId::default(),
+            weight: 0,
+        }
+    }
+
+    /// Sólo para las respuestas a las rutas que declara el módulo `handle`.
+    pub fn filter_by_referer_handle(mut self, handle: Handle) -> Self {
+        self.referer_handle = Some(handle);
+        self
+    }
+
+    /// Sólo para las respuestas a la ruta declarada como `id` (por ejemplo, `"/user/{id}"`).
+    pub fn filter_by_referer_id(mut self, id: impl Into<String>) -> Self {
+        self.referer_id.alter_value(id);
+        self
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+
+    #[inline(always)]
+    pub(crate) fn dispatch(request: &service::HttpRequest, headers: &mut HeaderMap) {
+        let pattern = request.match_pattern();
+        dispatch_filtered_actions(
+            Self::static_handle(),
+            pattern.as_deref().and_then(service::route_module),
+            pattern,
+            |action| (action_ref::<OnResponseFinalize>(&**action).f)(request, headers),
+        );
+    }
+}
@@ -1,7 +1,5 @@
 use crate::prelude::*;
 
-new_handle!(COMPONENT_BASE_ANCHOR);
-
 #[derive(Default)]
 pub enum AnchorType {
     #[default]
@@ -48,15 +46,13 @@ pub struct Anchor {
     target     : AnchorTarget,
 }
 
+impl_handle!(COMPONENT_BASE_ANCHOR for Anchor);
+
 impl ComponentTrait for Anchor {
     fn new() -> Self {
         Anchor::default()
     }
 
-    fn handle(&self) -> Handle {
-        COMPONENT_BASE_ANCHOR
-    }
-
     fn id(&self) -> Option<String> {
         self.id.get()
     }
@@ -85,9 +81,9 @@ impl ComponentTrait for Anchor {
                 href=[self.href().get()]
                 target=[target]
             {
-                (self.left_icon().prepare(cx))
+                (self.left_icon().render(cx))
                 " " span { (self.html().escaped(cx.langid())) } " "
-                (self.right_icon().prepare(cx))
+                (self.right_icon().render(cx))
             }
         })
     }
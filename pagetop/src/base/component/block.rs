@@ -1,36 +1,29 @@
 use crate::prelude::*;
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_BASE_BLOCK)]
 pub struct Block {
+    #[component(weight)]
     weight    : Weight,
+    #[component(renderable)]
     renderable: Renderable,
+    #[component(id)]
     id        : OptionId,
+    #[component(classes)]
     classes   : OptionClasses,
     title     : OptionTranslated,
     stuff     : ArcComponents,
+    #[component(template)]
     template  : String,
 }
 
-impl_handle!(COMPONENT_BASE_BLOCK for Block);
-
+#[component_trait]
 impl ComponentTrait for Block {
     fn new() -> Self {
         Block::default().with_classes(ClassesOp::Add, "block")
     }
 
-    fn id(&self) -> Option<String> {
-        self.id.get()
-    }
-
-    fn weight(&self) -> Weight {
-        self.weight
-    }
-
-    fn is_renderable(&self, cx: &Context) -> bool {
-        (self.renderable.check)(cx)
-    }
-
     fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
         let id = cx.required_id::<Block>(self.id());
         PrepareMarkup::With(html! {
@@ -49,30 +42,6 @@ impl ComponentTrait for Block {
 impl Block {
     // Block BUILDER.
 
-    #[fn_builder]
-    pub fn alter_id(&mut self, id: impl Into<String>) -> &mut Self {
-        self.id.alter_value(id);
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_weight(&mut self, value: Weight) -> &mut Self {
-        self.weight = value;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_renderable(&mut self, check: FnIsRenderable) -> &mut Self {
-        self.renderable.check = check;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_classes(&mut self, op: ClassesOp, classes: impl Into<String>) -> &mut Self {
-        self.classes.alter_value(op, classes);
-        self
-    }
-
     #[fn_builder]
     pub fn alter_title(&mut self, title: L10n) -> &mut Self {
         self.title.alter_value(title);
@@ -90,18 +59,8 @@ impl Block {
         self
     }
 
-    #[fn_builder]
-    pub fn alter_template(&mut self, template: &str) -> &mut Self {
-        self.template = template.to_owned();
-        self
-    }
-
     // Block GETTERS.
 
-    pub fn classes(&self) -> &OptionClasses {
-        &self.classes
-    }
-
     pub fn title(&self) -> &OptionTranslated {
         &self.title
     }
@@ -109,8 +68,4 @@ impl Block {
     pub fn components(&self) -> &ArcComponents {
         &self.stuff
     }
-
-    pub fn template(&self) -> &str {
-        self.template.as_str()
-    }
 }
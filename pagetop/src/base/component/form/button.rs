@@ -9,34 +9,30 @@ pub enum ButtonType {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_BASE_BUTTON)]
 pub struct Button {
+    #[component(weight)]
     weight     : Weight,
+    #[component(renderable)]
     renderable : Renderable,
+    #[component(classes)]
     classes    : OptionClasses,
     button_type: ButtonType,
     name       : OptionString,
     value      : OptionTranslated,
     autofocus  : OptionString,
     disabled   : OptionString,
+    #[component(template)]
     template   : String,
 }
 
-impl_handle!(COMPONENT_BASE_BUTTON for Button);
-
+#[component_trait]
 impl ComponentTrait for Button {
     fn new() -> Self {
         Button::default().with_classes(ClassesOp::Add, "btn btn-primary form-button")
     }
 
-    fn weight(&self) -> Weight {
-        self.weight
-    }
-
-    fn is_renderable(&self, cx: &Context) -> bool {
-        (self.renderable.check)(cx)
-    }
-
     fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
         let button_type = match self.button_type() {
             ButtonType::Button => "button",
@@ -83,24 +79,6 @@ impl Button {
 
     // Button BUILDER.
 
-    #[fn_builder]
-    pub fn alter_weight(&mut self, value: Weight) -> &mut Self {
-        self.weight = value;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_renderable(&mut self, check: FnIsRenderable) -> &mut Self {
-        self.renderable.check = check;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_classes(&mut self, op: ClassesOp, classes: impl Into<String>) -> &mut Self {
-        self.classes.alter_value(op, classes);
-        self
-    }
-
     #[fn_builder]
     pub fn alter_name(&mut self, name: &str) -> &mut Self {
         self.name.alter_value(name);
@@ -131,18 +109,8 @@ impl Button {
         self
     }
 
-    #[fn_builder]
-    pub fn alter_template(&mut self, template: &str) -> &mut Self {
-        self.template = template.to_owned();
-        self
-    }
-
     // Button GETTERS.
 
-    pub fn classes(&self) -> &OptionClasses {
-        &self.classes
-    }
-
     pub fn button_type(&self) -> &ButtonType {
         &self.button_type
     }
@@ -162,8 +130,4 @@ impl Button {
     pub fn disabled(&self) -> &OptionString {
         &self.disabled
     }
-
-    pub fn template(&self) -> &str {
-        self.template.as_str()
-    }
 }
@@ -8,21 +8,26 @@ pub enum FormMethod {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_BASE_FORM)]
 pub struct Form {
+    #[component(weight)]
     weight    : Weight,
+    #[component(renderable)]
     renderable: Renderable,
+    #[component(id)]
     id        : OptionId,
+    #[component(classes)]
     classes   : OptionClasses,
     action    : OptionString,
     charset   : OptionString,
     method    : FormMethod,
     stuff     : ArcComponents,
+    #[component(template)]
     template  : String,
 }
 
-impl_handle!(COMPONENT_BASE_FORM for Form);
-
+#[component_trait]
 impl ComponentTrait for Form {
     fn new() -> Self {
         Form::default()
@@ -30,22 +35,11 @@ impl ComponentTrait for Form {
             .with_charset("UTF-8")
     }
 
-    fn id(&self) -> Option<String> {
-        self.id.get()
-    }
-
-    fn weight(&self) -> Weight {
-        self.weight
-    }
-
-    fn is_renderable(&self, cx: &Context) -> bool {
-        (self.renderable.check)(cx)
-    }
-
     fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
-        let method = match self.method() {
-            FormMethod::Post => Some("post".to_owned()),
-            FormMethod::Get => None,
+        // Los formularios POST incluyen el token anti-falsificación de la sesión.
+        let (method, csrf_token) = match self.method() {
+            FormMethod::Post => (Some("post".to_owned()), Some(cx.csrf_token())),
+            FormMethod::Get => (None, None),
         };
         PrepareMarkup::With(html! {
             form
@@ -55,6 +49,9 @@ impl ComponentTrait for Form {
                 method=[method]
                 accept-charset=[self.charset().get()]
             {
+                @if let Some(csrf_token) = csrf_token {
+                    input type="hidden" name=(service::CSRF_FIELD) value=(csrf_token);
+                }
                 div { (self.elements().render(cx)) }
             }
         })
@@ -64,30 +61,6 @@ impl ComponentTrait for Form {
 impl Form {
     // Form BUILDER.
 
-    #[fn_builder]
-    pub fn alter_weight(&mut self, value: Weight) -> &mut Self {
-        self.weight = value;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_renderable(&mut self, check: FnIsRenderable) -> &mut Self {
-        self.renderable.check = check;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_id(&mut self, id: impl Into<String>) -> &mut Self {
-        self.id.alter_value(id);
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_classes(&mut self, op: ClassesOp, classes: impl Into<String>) -> &mut Self {
-        self.classes.alter_value(op, classes);
-        self
-    }
-
     #[fn_builder]
     pub fn alter_action(&mut self, action: &str) -> &mut Self {
         self.action.alter_value(action);
@@ -117,18 +90,8 @@ impl Form {
         self
     }
 
-    #[fn_builder]
-    pub fn alter_template(&mut self, template: &str) -> &mut Self {
-        self.template = template.to_owned();
-        self
-    }
-
     // Form GETTERS.
 
-    pub fn classes(&self) -> &OptionClasses {
-        &self.classes
-    }
-
     pub fn action(&self) -> &OptionString {
         &self.action
     }
@@ -144,8 +107,4 @@ impl Form {
     pub fn elements(&self) -> &ArcComponents {
         &self.stuff
     }
-
-    pub fn template(&self) -> &str {
-        self.template.as_str()
-    }
 }
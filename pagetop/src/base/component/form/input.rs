@@ -12,10 +12,14 @@ pub enum InputType {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_BASE_INPUT)]
 pub struct Input {
+    #[component(weight)]
     weight      : Weight,
+    #[component(renderable)]
     renderable  : Renderable,
+    #[component(classes)]
     classes     : OptionClasses,
     input_type  : InputType,
     name        : OptionName,
@@ -31,11 +35,11 @@ pub struct Input {
     readonly    : OptionString,
     required    : OptionString,
     help_text   : OptionTranslated,
+    #[component(template)]
     template    : String,
 }
 
-impl_handle!(COMPONENT_BASE_INPUT for Input);
-
+#[component_trait]
 impl ComponentTrait for Input {
     fn new() -> Self {
         Input::default()
@@ -44,14 +48,6 @@ impl ComponentTrait for Input {
             .with_maxlength(Some(128))
     }
 
-    fn weight(&self) -> Weight {
-        self.weight
-    }
-
-    fn is_renderable(&self, cx: &Context) -> bool {
-        (self.renderable.check)(cx)
-    }
-
     #[rustfmt::skip]
     fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
         let type_input = match self.input_type() {
@@ -150,24 +146,6 @@ impl Input {
 
     // Input BUILDER.
 
-    #[fn_builder]
-    pub fn alter_weight(&mut self, value: Weight) -> &mut Self {
-        self.weight = value;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_renderable(&mut self, check: FnIsRenderable) -> &mut Self {
-        self.renderable.check = check;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_classes(&mut self, op: ClassesOp, classes: impl Into<String>) -> &mut Self {
-        self.classes.alter_value(op, classes);
-        self
-    }
-
     #[fn_builder]
     pub fn alter_name(&mut self, name: &str) -> &mut Self {
         if let Some(previous) = self.name.get() {
@@ -265,18 +243,8 @@ impl Input {
         self
     }
 
-    #[fn_builder]
-    pub fn alter_template(&mut self, template: &str) -> &mut Self {
-        self.template = template.to_owned();
-        self
-    }
-
     // Input GETTERS.
 
-    pub fn classes(&self) -> &OptionClasses {
-        &self.classes
-    }
-
     pub fn input_type(&self) -> &InputType {
         &self.input_type
     }
@@ -332,8 +300,4 @@ impl Input {
     pub fn help_text(&self) -> &OptionTranslated {
         &self.help_text
     }
-
-    pub fn template(&self) -> &str {
-        self.template.as_str()
-    }
 }
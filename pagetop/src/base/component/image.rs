@@ -14,35 +14,27 @@ pub enum ImageSize {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_BASE_IMAGE)]
 pub struct Image {
+    #[component(weight)]
     weight    : Weight,
+    #[component(renderable)]
     renderable: Renderable,
+    #[component(id)]
     id        : OptionId,
+    #[component(classes)]
     classes   : OptionClasses,
     source    : OptionString,
     size      : ImageSize,
 }
 
-impl_handle!(COMPONENT_BASE_IMAGE for Image);
-
+#[component_trait]
 impl ComponentTrait for Image {
     fn new() -> Self {
         Image::default().with_classes(ClassesOp::Add, IMG_FLUID)
     }
 
-    fn id(&self) -> Option<String> {
-        self.id.get()
-    }
-
-    fn weight(&self) -> Weight {
-        self.weight
-    }
-
-    fn is_renderable(&self, cx: &Context) -> bool {
-        (self.renderable.check)(cx)
-    }
-
     fn prepare_component(&self, _cx: &mut Context) -> PrepareMarkup {
         let (width, height) = match self.size() {
             ImageSize::Auto => (None, None),
@@ -84,30 +76,6 @@ impl Image {
 
     // Image BUILDER.
 
-    #[fn_builder]
-    pub fn alter_weight(&mut self, value: Weight) -> &mut Self {
-        self.weight = value;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_renderable(&mut self, check: FnIsRenderable) -> &mut Self {
-        self.renderable.check = check;
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_id(&mut self, id: impl Into<String>) -> &mut Self {
-        self.id.alter_value(id);
-        self
-    }
-
-    #[fn_builder]
-    pub fn alter_classes(&mut self, op: ClassesOp, classes: impl Into<String>) -> &mut Self {
-        self.classes.alter_value(op, classes);
-        self
-    }
-
     #[fn_builder]
     pub fn alter_source(&mut self, source: &str) -> &mut Self {
         self.source.alter_value(source);
@@ -122,10 +90,6 @@ impl Image {
 
     // Image GETTERS.
 
-    pub fn classes(&self) -> &OptionClasses {
-        &self.classes
-    }
-
     pub fn source(&self) -> &OptionString {
         &self.source
     }
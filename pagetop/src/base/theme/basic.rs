@@ -5,6 +5,8 @@ pub struct Basic;
 impl_handle!(THEME_BASIC for Basic);
 
 impl ModuleTrait for Basic {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::n("Basic")
     }
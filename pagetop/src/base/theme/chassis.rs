@@ -5,6 +5,8 @@ pub struct Chassis;
 impl_handle!(THEME_CHASSIS for Chassis);
 
 impl ModuleTrait for Chassis {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::n("Chassis")
     }
@@ -5,6 +5,8 @@ pub struct Inception;
 impl_handle!(THEME_INCEPTION for Inception);
 
 impl ModuleTrait for Inception {
+    module_version!();
+
     fn name(&self) -> L10n {
         L10n::n("Inception")
     }
@@ -123,8 +123,9 @@ use crate::config::data::ConfigData;
 use crate::config::file::File;
 use crate::{concat_string, LazyStatic};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use std::collections::HashMap;
 use std::env;
 
 /// Directorio donde se encuentran los archivos de configuración.
@@ -195,18 +196,21 @@ macro_rules! default_settings {
     };
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Configuration settings for the [`[app]`](App), [`[database]`](Database), [`[dev]`](Dev),
-/// [`[log]`](Log), and [`[server]`](Server) sections (see [`SETTINGS`]).
+/// [`[log]`](Log), [`[rate_limit]`](RateLimit), [`[security]`](Security), and
+/// [`[server]`](Server) sections (see [`SETTINGS`]).
 pub struct Settings {
     pub app: App,
     pub database: Database,
     pub dev: Dev,
     pub log: Log,
+    pub rate_limit: RateLimit,
+    pub security: Security,
     pub server: Server,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Section `[app]` of the configuration settings.
 ///
 /// See [`Settings`].
@@ -234,7 +238,7 @@ pub struct App {
     pub run_mode: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Section `[database]` of the configuration settings.
 ///
 /// See [`Settings`].
@@ -262,7 +266,7 @@ pub struct Database {
     pub max_pool_size: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Section `[dev]` of the configuration settings.
 ///
 /// See [`Settings`].
@@ -275,7 +279,7 @@ pub struct Dev {
     pub pagetop_project_dir: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 /// Section `[log]` of the configuration settings.
 ///
 /// See [`Settings`].
@@ -295,12 +299,87 @@ pub struct Log {
     /// Prefijo para los archivos de traza (si `rolling` != *"Stdout"*).
     /// Por defecto: *"tracing.log"*.
     pub prefix: String,
-    /// Presentación de las trazas. Puede ser *"Full"*, *"Compact"*, *"Pretty"* o *"Json"*.
+    /// Presentación de las trazas. Puede ser *"Full"*, *"Compact"*, *"Pretty"* o *"Json"*. En todas
+    /// ellas las trazas de cada petición incluyen su identificador en el campo `request_id`.
     /// Por defecto: *"Full"*.
     pub format: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
+/// Section `[rate_limit]` of the configuration settings.
+///
+/// See [`Settings`].
+pub struct RateLimit {
+    /// Habilita la limitación de peticiones.
+    /// Por defecto: *true*.
+    pub enabled: bool,
+    /// Usa la dirección del cliente que indican las cabeceras *Forwarded* o *X-Forwarded-For*. Sólo
+    /// debe habilitarse si la aplicación se sirve tras un *proxy* de confianza.
+    /// Por defecto: *false*.
+    pub trust_proxy_headers: bool,
+    /// Límites para las rutas que empiezan por cada prefijo. Sustituyen a los que declaren los
+    /// módulos para el mismo prefijo y, si varios prefijos coinciden, se aplica el más largo.
+    /// Por defecto: *sin límites*.
+    #[serde(default)]
+    pub routes: HashMap<String, RateLimitRoute>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Per-route limits for the [`[rate_limit]`](RateLimit) section.
+pub struct RateLimitRoute {
+    /// Número máximo de peticiones seguidas. El valor 0 deja la ruta sin límite.
+    pub requests: u32,
+    /// Segundos para recuperar el máximo de peticiones.
+    pub seconds: u64,
+    /// Criterio para identificar al cliente: *"ip"* o *"session"*.
+    /// Por defecto: *"ip"*.
+    #[serde(default)]
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+/// Section `[security]` of the configuration settings.
+///
+/// See [`Settings`].
+pub struct Security {
+    /// Política de seguridad del contenido (*Content-Security-Policy*). Las fuentes de los
+    /// *scripts* y estilos de cada página se añaden a `script-src` y `style-src` según los recursos
+    /// registrados en su contexto.
+    /// Por defecto: *"default-src 'self'; img-src 'self' data:; object-src 'none'; base-uri 'self';
+    /// frame-ancestors 'self'; style-src-attr 'unsafe-inline'"*.
+    pub content_security_policy: String,
+    /// Cabecera *Strict-Transport-Security* (sólo se envía en conexiones HTTPS).
+    /// Por defecto: *"max-age=31536000; includeSubDomains"*.
+    pub strict_transport_security: String,
+    /// Cabecera *X-Frame-Options*.
+    /// Por defecto: *"SAMEORIGIN"*.
+    pub x_frame_options: String,
+    /// Cabecera *Referrer-Policy*.
+    /// Por defecto: *"strict-origin-when-cross-origin"*.
+    pub referrer_policy: String,
+    /// Cabecera *Permissions-Policy*.
+    /// Por defecto: *"camera=(), geolocation=(), microphone=()"*.
+    pub permissions_policy: String,
+    /// Valores que sustituyen a los anteriores para las rutas que empiezan por cada prefijo. Si
+    /// varios prefijos coinciden se aplica el más largo.
+    /// Por defecto: *sin sustituciones*.
+    #[serde(default)]
+    pub routes: HashMap<String, SecurityRoute>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+/// Per-route overrides for the [`[security]`](Security) section.
+///
+/// Un valor vacío suprime la cabecera en las rutas afectadas.
+pub struct SecurityRoute {
+    pub content_security_policy: Option<String>,
+    pub strict_transport_security: Option<String>,
+    pub x_frame_options: Option<String>,
+    pub referrer_policy: Option<String>,
+    pub permissions_policy: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
 /// Section `[server]` of the configuration settings.
 ///
 /// See [`Settings`].
@@ -311,41 +390,161 @@ pub struct Server {
     /// Puerto del servidor web.
     /// Por defecto: *8088*.
     pub bind_port: u16,
+    /// Ruta al archivo PEM con la cadena de certificados para servir HTTPS en `bind_port`
+    /// (requiere la característica `tls`).
+    /// Por defecto: *""* (sólo HTTP).
+    pub tls_cert_path: String,
+    /// Ruta al archivo PEM con la clave privada del certificado.
+    /// Por defecto: *""*.
+    pub tls_key_path: String,
+    /// Puerto adicional en texto plano que redirige permanentemente a HTTPS (0 para no usarlo).
+    /// Por defecto: *0*.
+    pub redirect_port: u16,
     /// Duración en segundos para la sesión (0 indica "hasta que se cierre el navegador").
     /// Por defecto: *604800* (7 días).
     pub session_lifetime: i64,
+    /// Número de procesos (*workers*) para atender peticiones (0 para usar tantos como núcleos
+    /// físicos tenga la CPU).
+    /// Por defecto: *0*.
+    pub workers: usize,
+    /// Tiempo en segundos que se mantiene abierta una conexión sin actividad (0 para cerrar las
+    /// conexiones tras cada petición).
+    /// Por defecto: *5*.
+    pub keep_alive: u64,
+    /// Tiempo máximo en milisegundos para recibir las cabeceras de una petición.
+    /// Por defecto: *5000*.
+    pub client_request_timeout: u64,
+    /// Tiempo en segundos para terminar las peticiones en curso al detener el servidor.
+    /// Por defecto: *30*.
+    pub shutdown_timeout: u64,
+    /// Número máximo de conexiones pendientes de aceptar.
+    /// Por defecto: *1024*.
+    pub backlog: u32,
+    /// Número máximo de conexiones simultáneas por *worker*.
+    /// Por defecto: *25000*.
+    pub max_connections: usize,
+    /// Tamaño máximo en bytes del cuerpo de las peticiones, incluidos los formularios.
+    /// Por defecto: *262144* (256 KiB).
+    pub max_payload_size: usize,
+    /// Clave maestra para cifrar las cookies de sesión, de al menos 32 bytes. Si no se indica esta
+    /// clave ni `session_key_file` se genera una clave temporal, pero sólo para los modos de
+    /// ejecución *"dev"*, *"devel"* o *"development"*.
+    /// Por defecto: *""*.
+    pub session_key: String,
+    /// Ruta al archivo con la clave maestra para las cookies de sesión (alternativa a
+    /// `session_key`).
+    /// Por defecto: *""*.
+    pub session_key_file: String,
+    /// Clave maestra anterior que se sigue aceptando para rotar las claves sin cerrar las sesiones
+    /// abiertas.
+    /// Por defecto: *""*.
+    pub session_key_old: String,
+    /// Ruta al archivo con la clave maestra anterior (alternativa a `session_key_old`).
+    /// Por defecto: *""*.
+    pub session_key_old_file: String,
+    /// Almacén para los datos de sesión: *"Cookie"* (en la propia cookie de sesión), *"Memory"*
+    /// (en memoria), *"File"* (en archivos) o *"Database"* (en la base de datos, requiere la
+    /// característica `database`).
+    /// Por defecto: *"Cookie"*.
+    pub session_store: String,
+    /// Directorio para los archivos de sesión (si `session_store` = *"File"*).
+    /// Por defecto: *"sessions"*.
+    pub session_store_path: String,
+    /// Intervalo en segundos para eliminar las sesiones caducadas de los almacenes en el servidor
+    /// (0 para no eliminarlas).
+    /// Por defecto: *3600* (1 hora).
+    pub session_sweep_interval: u64,
+    /// Ruta para comprobar que la aplicación está viva (*liveness*), vacía para no usarla.
+    /// Por defecto: *"/health"*.
+    pub health_path: String,
+    /// Ruta para comprobar que la aplicación puede atender peticiones (*readiness*), vacía para no
+    /// usarla.
+    /// Por defecto: *"/ready"*.
+    pub ready_path: String,
+    /// Activa el modo mantenimiento, que responde a las peticiones con un error 503.
+    /// Por defecto: *false*.
+    pub maintenance_mode: bool,
+    /// Archivo que, mientras exista, activa también el modo mantenimiento (vacío para no usarlo).
+    /// Por defecto: *"maintenance.flag"*.
+    pub maintenance_flag_file: String,
+    /// Rutas, separadas por comas, que siguen disponibles en modo mantenimiento. Las rutas de
+    /// `health_path`, `ready_path` y de los archivos estáticos siempre lo están.
+    /// Por defecto: *"/admin"*.
+    pub maintenance_allowed_paths: String,
+    /// Segundos que se sugiere esperar en la cabecera *Retry-After* (0 para no enviarla).
+    /// Por defecto: *3600*.
+    pub maintenance_retry_after: u64,
+    /// Ejecuta las tareas programadas de los módulos en el propio servidor web. Se puede desactivar
+    /// para ejecutarlas desde un programador externo con el subcomando `cron run`.
+    /// Por defecto: *true*.
+    pub scheduled_tasks: bool,
 }
 
 default_settings!(
     // [app]
-    "app.name"                => "PageTop App",
-    "app.description"         => "Developed with the awesome PageTop framework.",
-    "app.theme"               => "Default",
-    "app.language"            => "en-US",
-    "app.direction"           => "ltr",
-    "app.startup_banner"      => "Slant",
+    "app.name"                           => "PageTop App",
+    "app.description"                    => "Developed with the awesome PageTop framework.",
+    "app.theme"                          => "Default",
+    "app.language"                       => "en-US",
+    "app.direction"                      => "ltr",
+    "app.startup_banner"                 => "Slant",
 
     // [database]
-    "database.db_type"        => "",
-    "database.db_name"        => "",
-    "database.db_user"        => "",
-    "database.db_pass"        => "",
-    "database.db_host"        => "localhost",
-    "database.db_port"        => 0,
-    "database.max_pool_size"  => 5,
+    "database.db_type"                   => "",
+    "database.db_name"                   => "",
+    "database.db_user"                   => "",
+    "database.db_pass"                   => "",
+    "database.db_host"                   => "localhost",
+    "database.db_port"                   => 0,
+    "database.max_pool_size"             => 5,
 
     // [dev]
-    "dev.pagetop_project_dir" => "",
+    "dev.pagetop_project_dir"            => "",
 
     // [log]
-    "log.tracing"             => "Info",
-    "log.rolling"             => "Stdout",
-    "log.path"                => "log",
-    "log.prefix"              => "tracing.log",
-    "log.format"              => "Full",
+    "log.tracing"                        => "Info",
+    "log.rolling"                        => "Stdout",
+    "log.path"                           => "log",
+    "log.prefix"                         => "tracing.log",
+    "log.format"                         => "Full",
+
+    // [rate_limit]
+    "rate_limit.enabled"                 => true,
+    "rate_limit.trust_proxy_headers"     => false,
+
+    // [security]
+    "security.content_security_policy"   => "default-src 'self'; img-src 'self' data:; object-src 'none'; base-uri 'self'; frame-ancestors 'self'; style-src-attr 'unsafe-inline'",
+    "security.strict_transport_security" => "max-age=31536000; includeSubDomains",
+    "security.x_frame_options"           => "SAMEORIGIN",
+    "security.referrer_policy"           => "strict-origin-when-cross-origin",
+    "security.permissions_policy"        => "camera=(), geolocation=(), microphone=()",
 
     // [server]
-    "server.bind_address"     => "localhost",
-    "server.bind_port"        => 8088,
-    "server.session_lifetime" => 604800,
+    "server.bind_address"                => "localhost",
+    "server.bind_port"                   => 8088,
+    "server.tls_cert_path"               => "",
+    "server.tls_key_path"                => "",
+    "server.redirect_port"               => 0,
+    "server.session_lifetime"            => 604800,
+    "server.workers"                     => 0,
+    "server.keep_alive"                  => 5,
+    "server.client_request_timeout"      => 5000,
+    "server.shutdown_timeout"            => 30,
+    "server.backlog"                     => 1024,
+    "server.max_connections"             => 25000,
+    "server.max_payload_size"            => 262144,
+    "server.session_key"                 => "",
+    "server.session_key_file"            => "",
+    "server.session_key_old"             => "",
+    "server.session_key_old_file"        => "",
+    "server.session_store"               => "Cookie",
+    "server.session_store_path"          => "sessions",
+    "server.session_sweep_interval"      => 3600,
+    "server.health_path"                 => "/health",
+    "server.ready_path"                  => "/ready",
+    "server.maintenance_mode"            => false,
+    "server.maintenance_flag_file"       => "maintenance.flag",
+    "server.maintenance_allowed_paths"   => "/admin",
+    "server.maintenance_retry_after"     => 3600,
+    "server.scheduled_tasks"             => true,
 );
@@ -1,10 +1,18 @@
 mod definition;
 pub use definition::{action_ref, ActionBase, ActionTrait};
 
+mod result;
+pub use result::ActionResult;
+
 mod list;
 pub use list::Action;
 use list::ActionsList;
 
 mod all;
-pub(crate) use all::add_action;
-pub use all::{dispatch_actions, KeyAction};
+pub(crate) use all::{action_handles, add_action, dispatch_merged_alter_actions};
+pub use all::{dispatch_actions, dispatch_alter_actions, KeyAction};
+pub use all::{registered_actions, ActionInfo};
+
+mod event;
+pub use event::ACTION_ON_EVENT;
+pub use event::{publish_event, EventFuture, EventTrait, FnEvent, FnEventAsync, OnEvent};
@@ -1,7 +1,8 @@
-use crate::core::action::{Action, ActionsList};
-use crate::{Handle, LazyStatic};
+use crate::core::action::{Action, ActionResult, ActionsList};
+use crate::{util, Handle, LazyStatic, Weight};
 
 use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::sync::RwLock;
 
 pub type KeyAction = (Handle, Option<Handle>, Option<String>);
@@ -10,7 +11,69 @@ pub type KeyAction = (Handle, Option<Handle>, Option<String>);
 static ACTIONS: LazyStatic<RwLock<HashMap<KeyAction, ActionsList>>> =
     LazyStatic::new(|| RwLock::new(HashMap::new()));
 
-pub fn add_action(action: Action) {
+/// Datos de una acción registrada, para consultar qué acciones hay y a qué se aplican.
+#[derive(Clone)]
+pub struct ActionInfo {
+    module: &'static str,
+    handle: Handle,
+    type_name: &'static str,
+    referer_handle: Option<Handle>,
+    referer_type_name: Option<&'static str>,
+    referer_id: Option<String>,
+    weight: Weight,
+}
+
+impl ActionInfo {
+    // ActionInfo GETTERS.
+
+    /// Nombre del módulo que registra la acción.
+    pub fn module(&self) -> &'static str {
+        self.module
+    }
+
+    pub fn handle(&self) -> Handle {
+        self.handle
+    }
+
+    /// Nombre legible del tipo de la acción.
+    pub fn name(&self) -> String {
+        util::handle_name(self.handle).unwrap_or_else(|| self.handle.to_string())
+    }
+
+    pub fn referer_handle(&self) -> Option<Handle> {
+        self.referer_handle
+    }
+
+    /// Nombre legible del tipo al que se refiere la acción, si se aplica sólo a uno.
+    pub fn referer_name(&self) -> Option<String> {
+        self.referer_handle
+            .map(|h| util::handle_name(h).unwrap_or_else(|| h.to_string()))
+    }
+
+    pub fn referer_id(&self) -> Option<&str> {
+        self.referer_id.as_deref()
+    }
+
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+// Datos de las acciones registradas, en orden de registro.
+static ACTIONS_INFO: LazyStatic<RwLock<Vec<ActionInfo>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
+pub fn add_action(module: &'static str, action: Action) {
+    ACTIONS_INFO.write().unwrap().push(ActionInfo {
+        module,
+        handle: action.handle(),
+        type_name: action.type_name(),
+        referer_handle: action.referer_handle(),
+        referer_type_name: action.referer_type_name(),
+        referer_id: action.referer_id(),
+        weight: action.weight(),
+    });
+
     let mut actions = ACTIONS.write().unwrap();
     let key_action = (
         action.handle(),
@@ -24,6 +87,24 @@ pub fn add_action(action: Action) {
     }
 }
 
+/// Devuelve los datos de todas las acciones registradas por los módulos habilitados, en el orden en
+/// que se registran.
+pub fn registered_actions() -> Vec<ActionInfo> {
+    ACTIONS_INFO.read().unwrap().clone()
+}
+
+// Identificadores de las acciones registradas, y de los tipos a los que se refieren, con sus tipos.
+pub(crate) fn action_handles() -> Vec<(Handle, &'static str)> {
+    let mut handles = Vec::new();
+    for a in ACTIONS_INFO.read().unwrap().iter() {
+        handles.push((a.handle, a.type_name));
+        if let (Some(handle), Some(type_name)) = (a.referer_handle, a.referer_type_name) {
+            handles.push((handle, type_name));
+        }
+    }
+    handles
+}
+
 pub fn dispatch_actions<B, F>(key_action: KeyAction, f: F)
 where
     F: FnMut(&Action) -> B,
@@ -32,3 +113,48 @@ where
         list.iter_map(f)
     }
 }
+
+/// Ejecuta en cadena las acciones registradas para `key_action`, pasando a cada una el valor que deja
+/// la anterior, empezando por `value`.
+///
+/// Devuelve [`ControlFlow::Break`] con el valor final si alguna acción ha detenido la cadena
+/// ([`ActionResult::Stop`] o [`ActionResult::Return`]), o [`ControlFlow::Continue`] en otro caso.
+pub fn dispatch_alter_actions<T, F>(key_action: KeyAction, value: T, f: F) -> ControlFlow<T, T>
+where
+    F: FnMut(&Action, &T) -> ActionResult<T>,
+{
+    match ACTIONS.read().unwrap().get(&key_action) {
+        Some(list) => list.alter_chain(value, f),
+        None => ControlFlow::Continue(value),
+    }
+}
+
+// Como `dispatch_alter_actions()`, pero ejecuta en una sola cadena y en orden de peso las acciones
+// registradas para cualquiera de las claves `keys`. Con el mismo peso se ejecutan antes las de las
+// primeras claves.
+pub(crate) fn dispatch_merged_alter_actions<T, F>(
+    keys: &[KeyAction],
+    mut value: T,
+    mut f: F,
+) -> ControlFlow<T, T>
+where
+    F: FnMut(&Action, &T) -> ActionResult<T>,
+{
+    let actions = ACTIONS.read().unwrap();
+    let lists: Vec<_> = keys
+        .iter()
+        .filter_map(|key_action| actions.get(key_action))
+        .map(|list| list.read())
+        .collect();
+    let mut merged: Vec<&Action> = lists.iter().flat_map(|list| list.iter()).collect();
+    merged.sort_by_key(|action| action.weight());
+    for action in merged.into_iter() {
+        match f(action, &value) {
+            ActionResult::Continue => {}
+            ActionResult::Replace(new_value) => value = new_value,
+            ActionResult::Stop => return ControlFlow::Break(value),
+            ActionResult::Return(new_value) => return ControlFlow::Break(new_value),
+        }
+    }
+    ControlFlow::Continue(value)
+}
@@ -15,6 +15,12 @@ pub trait ActionTrait: ActionBase + HasHandle + Send + Sync {
         None
     }
 
+    /// Nombre del tipo al que se refiere la acción, si el identificador de
+    /// [`referer_handle()`](Self::referer_handle) corresponde a un tipo genérico de la acción.
+    fn referer_type_name(&self) -> Option<&'static str> {
+        None
+    }
+
     fn weight(&self) -> Weight {
         0
     }
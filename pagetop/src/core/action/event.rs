@@ -0,0 +1,186 @@
+use crate::core::action::{action_ref, dispatch_actions, ActionTrait};
+use crate::{impl_handle, trace, util, Handle, HasHandle, Weight};
+
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Los eventos deben implementar este *trait*.
+///
+/// Un evento es un tipo con los datos de algo que ha ocurrido en un módulo, como *"usuario
+/// identificado"* o *"contenido guardado"*, al que otros módulos pueden suscribirse con
+/// [`OnEvent`] sin depender de los detalles internos del módulo que lo publica.
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// #[derive(Clone)]
+/// pub struct UserLoggedIn {
+///     pub name: String,
+/// }
+///
+/// impl_handle!(EVENT_USER_LOGGED_IN for UserLoggedIn);
+///
+/// impl EventTrait for UserLoggedIn {}
+///
+/// async fn login(name: &str) {
+///     publish_event(&UserLoggedIn { name: name.to_owned() }).await;
+/// }
+/// ```
+pub trait EventTrait: HasHandle + Clone + Send + Sync + 'static {}
+
+/// Futuro que devuelven los suscriptores asíncronos.
+pub type EventFuture = Pin<Box<dyn Future<Output = Result<(), String>>>>;
+
+/// Suscriptor síncrono. Si falla devuelve la descripción del error.
+pub type FnEvent<E> = fn(event: &E) -> Result<(), String>;
+
+/// Suscriptor asíncrono, que recibe una copia del evento.
+pub type FnEventAsync<E> = fn(event: E) -> EventFuture;
+
+enum Subscriber<E> {
+    Sync(FnEvent<E>),
+    Async(FnEventAsync<E>),
+}
+
+impl<E> Clone for Subscriber<E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E> Copy for Subscriber<E> {}
+
+/// Acción para suscribirse a un evento desde [`ModuleTrait::actions()`].
+///
+/// Los suscriptores de cada evento se ejecutan en orden de peso (primero los de menor peso). Si uno
+/// falla o entra en pánico se registra el error en las trazas y se siguen
+/// ejecutando los demás.
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// #[derive(Clone)]
+/// pub struct NodeSaved {
+///     pub id: u64,
+/// }
+///
+/// impl_handle!(EVENT_NODE_SAVED for NodeSaved);
+///
+/// impl EventTrait for NodeSaved {}
+///
+/// struct Search;
+///
+/// impl_handle!(MODULE_SEARCH for Search);
+///
+/// impl ModuleTrait for Search {
+///     fn actions(&self) -> Vec<Action> {
+///         actions![
+///             OnEvent::<NodeSaved>::with(log_node),
+///             OnEvent::<NodeSaved>::with_async(reindex_node).with_weight(10),
+///         ]
+///     }
+/// }
+///
+/// fn log_node(event: &NodeSaved) -> Result<(), String> {
+///     Ok(())
+/// }
+///
+/// fn reindex_node(event: NodeSaved) -> EventFuture {
+///     Box::pin(async move { Ok(()) })
+/// }
+/// ```
+///
+/// [`ModuleTrait::actions()`]: crate::core::module::ModuleTrait::actions
+pub struct OnEvent<E: EventTrait> {
+    subscriber: Subscriber<E>,
+    weight: Weight,
+}
+
+impl_handle!(ACTION_ON_EVENT for OnEvent<EventTrait>);
+
+impl<E: EventTrait> ActionTrait for OnEvent<E> {
+    fn referer_handle(&self) -> Option<Handle> {
+        Some(E::static_handle())
+    }
+
+    fn referer_type_name(&self) -> Option<&'static str> {
+        Some(std::any::type_name::<E>())
+    }
+
+    fn weight(&self) -> Weight {
+        self.weight
+    }
+}
+
+impl<E: EventTrait> OnEvent<E> {
+    pub fn with(f: FnEvent<E>) -> Self {
+        OnEvent {
+            subscriber: Subscriber::Sync(f),
+            weight: 0,
+        }
+    }
+
+    pub fn with_async(f: FnEventAsync<E>) -> Self {
+        OnEvent {
+            subscriber: Subscriber::Async(f),
+            weight: 0,
+        }
+    }
+
+    pub fn with_weight(mut self, value: Weight) -> Self {
+        self.weight = value;
+        self
+    }
+}
+
+/// Publica el evento, ejecutando en orden de peso todos los suscriptores registrados con
+/// [`OnEvent`]. Devuelve el número de suscriptores que han fallado.
+pub async fn publish_event<E: EventTrait>(event: &E) -> usize {
+    // Se copian los suscriptores para no mantener bloqueado el registro de acciones mientras se
+    // ejecutan los asíncronos.
+    let mut subscribers = Vec::new();
+    dispatch_actions(
+        (
+            OnEvent::<E>::static_handle(),
+            Some(E::static_handle()),
+            None,
+        ),
+        |action| subscribers.push(action_ref::<OnEvent<E>>(&**action).subscriber),
+    );
+
+    let mut failed = 0;
+    for subscriber in subscribers.into_iter() {
+        let result = match subscriber {
+            Subscriber::Sync(f) => catch_unwind(AssertUnwindSafe(|| f(event)))
+                .unwrap_or_else(|_| Err("subscriber panicked".to_owned())),
+            Subscriber::Async(f) => match catch_unwind(AssertUnwindSafe(|| f(event.clone()))) {
+                Ok(future) => CatchUnwind(future).await,
+                Err(_) => Err("subscriber panicked".to_owned()),
+            },
+        };
+        if let Err(e) = result {
+            failed += 1;
+            trace::error!(
+                "Subscriber to event \"{}\" failed ({})",
+                util::single_type_name::<E>(),
+                e
+            );
+        }
+    }
+    failed
+}
+
+// Futuro de un suscriptor asíncrono que devuelve un error si entra en pánico.
+struct CatchUnwind(EventFuture);
+
+impl Future for CatchUnwind {
+    type Output = Result<(), String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = &mut self.get_mut().0;
+        catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx)))
+            .unwrap_or_else(|_| Poll::Ready(Err("subscriber panicked".to_owned())))
+    }
+}
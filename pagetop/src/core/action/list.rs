@@ -1,6 +1,7 @@
-use crate::core::action::ActionTrait;
+use crate::core::action::{ActionResult, ActionTrait};
 
-use std::sync::{Arc, RwLock};
+use std::ops::ControlFlow;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 pub type Action = Box<dyn ActionTrait>;
 
@@ -23,6 +24,10 @@ impl ActionsList {
         list.sort_by_key(|a| a.weight());
     }
 
+    pub(crate) fn read(&self) -> RwLockReadGuard<'_, Vec<Action>> {
+        self.0.read().unwrap()
+    }
+
     pub fn iter_map<B, F>(&self, f: F)
     where
         Self: Sized,
@@ -30,6 +35,21 @@ impl ActionsList {
     {
         let _: Vec<_> = self.0.read().unwrap().iter().map(f).collect();
     }
+
+    pub fn alter_chain<T, F>(&self, mut value: T, mut f: F) -> ControlFlow<T, T>
+    where
+        F: FnMut(&Action, &T) -> ActionResult<T>,
+    {
+        for action in self.0.read().unwrap().iter() {
+            match f(action, &value) {
+                ActionResult::Continue => {}
+                ActionResult::Replace(new_value) => value = new_value,
+                ActionResult::Stop => return ControlFlow::Break(value),
+                ActionResult::Return(new_value) => return ControlFlow::Break(new_value),
+            }
+        }
+        ControlFlow::Continue(value)
+    }
 }
 
 #[macro_export]
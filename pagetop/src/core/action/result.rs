@@ -0,0 +1,14 @@
+/// Resultado de una acción en una cadena de alteraciones.
+///
+/// Cada acción de la cadena recibe el valor que ha dejado la acción anterior y decide si la cadena
+/// continúa y con qué valor. Ver [`dispatch_alter_actions()`](crate::core::action::dispatch_alter_actions).
+pub enum ActionResult<T> {
+    /// Continúa con la siguiente acción sin cambiar el valor.
+    Continue,
+    /// Continúa con la siguiente acción usando el nuevo valor.
+    Replace(T),
+    /// No ejecuta más acciones y mantiene el valor.
+    Stop,
+    /// No ejecuta más acciones y devuelve el nuevo valor.
+    Return(T),
+}
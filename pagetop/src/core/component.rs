@@ -1,12 +1,18 @@
 mod context;
+pub(crate) use context::ContentSources;
 pub use context::{Context, ContextOp};
 pub type FnContextualPath = fn(cx: &Context) -> &str;
 
 mod renderable;
 pub use renderable::{FnIsRenderable, Renderable};
 
+mod cache;
+pub use cache::{clear_render_cache, invalidate_cache_tags, set_render_cache_store};
+pub use cache::{CacheContext, CachedRender, MemoryRenderCache, RenderCache, RenderCacheStore};
+
 mod definition;
-pub use definition::{component_as_mut, component_as_ref, ComponentBase, ComponentTrait};
+pub use definition::{component_as_mut, component_as_ref};
+pub use definition::{ComponentBase, ComponentFields, ComponentTrait};
 
 mod arc;
 pub use arc::{ArcComponent, ArcComponents, ArcOp};
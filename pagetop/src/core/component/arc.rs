@@ -1,6 +1,6 @@
 use crate::core::component::{ComponentTrait, Context};
 use crate::html::{html, Markup};
-use crate::{impl_handle, Handle, Weight};
+use crate::{impl_handle, util, Handle, Weight};
 
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 
@@ -146,6 +146,23 @@ impl ArcComponents {
         self.0.iter().filter(move |&c| c.handle() == handle)
     }
 
+    pub(crate) fn type_handles(&self) -> Vec<(Handle, &'static str)> {
+        self.0
+            .iter()
+            .map(|arc| {
+                let component = arc.get();
+                (component.handle(), component.type_name())
+            })
+            .collect()
+    }
+
+    pub(crate) fn type_names(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|arc| util::short_type_name(arc.get().type_name()))
+            .collect()
+    }
+
     // ArcComponents RENDER.
 
     pub fn render(&self, cx: &mut Context) -> Markup {
@@ -0,0 +1,303 @@
+use crate::core::component::context::{RecordedOp, Recording};
+use crate::core::component::Context;
+use crate::html::Markup;
+use crate::service::session_roles;
+use crate::{concat_string, LazyStatic};
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// Datos de la petición de los que depende el código renderizado. Cada combinación de valores se
+/// guarda en la caché por separado.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum CacheContext {
+    /// Idioma del contexto.
+    LangId,
+    /// Tema del contexto.
+    Theme,
+    /// Ruta declarada que atiende la petición (por ejemplo, `"/user/{id}"`), o la ruta de la
+    /// petición si no coincide con ninguna.
+    Route,
+    /// Roles de la sesión de la petición.
+    Roles,
+}
+
+/// Configuración de la caché del código renderizado de un componente o de una región.
+///
+/// Los componentes la declaran en
+/// [`ComponentTrait::render_cache()`](crate::core::component::ComponentTrait::render_cache), y los
+/// temas para las regiones en
+/// [`ThemeTrait::region_cache()`](crate::core::theme::ThemeTrait::region_cache). Mientras el código
+/// está en caché no se vuelve a preparar ni se ejecutan las acciones del tema y de los módulos
+/// sobre el componente, pero se vuelven a aplicar al contexto los recursos (hojas de estilo,
+/// *scripts*, etc.) y los parámetros que se añadieron al renderizarlo.
+///
+/// No se guarda el código que incluye datos propios de la sesión, como el token anti-falsificación
+/// de los formularios (ver [`Context::csrf_token()`]) o lo que se marque con
+/// [`Context::set_uncacheable()`].
+///
+/// Las instancias de un mismo componente se distinguen por su identificador. Si hay varias sin
+/// identificador y con distinto contenido, deben diferenciarse con [`with_key()`](Self::with_key).
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// #[derive(Default)]
+/// struct LatestNodes;
+///
+/// impl_handle!(COMPONENT_LATEST_NODES for LatestNodes);
+///
+/// impl ComponentTrait for LatestNodes {
+///     fn new() -> Self {
+///         LatestNodes
+///     }
+///
+///     fn render_cache(&self) -> Option<RenderCache> {
+///         Some(
+///             RenderCache::new()
+///                 .with_context(CacheContext::LangId)
+///                 .with_max_age(std::time::Duration::from_secs(600))
+///                 .with_tag("node"),
+///         )
+///     }
+/// }
+///
+/// // Al guardar un contenido, por ejemplo desde un suscriptor del evento correspondiente.
+/// invalidate_cache_tags(&["node"]);
+/// ```
+#[derive(Clone, Default)]
+pub struct RenderCache {
+    contexts: Vec<CacheContext>,
+    max_age: Option<Duration>,
+    tags: Vec<String>,
+    keys: Vec<String>,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        RenderCache::default()
+    }
+
+    // RenderCache BUILDER.
+
+    pub fn with_context(mut self, context: CacheContext) -> Self {
+        if !self.contexts.contains(&context) {
+            self.contexts.push(context);
+        }
+        self
+    }
+
+    /// Tiempo máximo que se conserva en caché. Por defecto se conserva hasta que se invalida.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Etiqueta para invalidar el código en caché con [`invalidate_cache_tags()`].
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Clave adicional para distinguir el código en caché de instancias con distinto contenido.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into());
+        self
+    }
+
+    // RenderCache GETTERS.
+
+    pub fn contexts(&self) -> &[CacheContext] {
+        &self.contexts
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    // RenderCache RENDER.
+
+    // Devuelve el código en caché, volviendo a aplicar sus cambios al contexto. Si no está en
+    // caché devuelve la clave para guardarlo con store(), y empieza a registrar los cambios que se
+    // hagan en el contexto.
+    pub(crate) fn lookup(&self, base: &str, cx: &mut Context) -> Result<Markup, String> {
+        let key = self.key(base, cx);
+        let cached = RENDER_CACHE.read().unwrap().get(&key);
+        if let Some(cached) = cached {
+            if !cached.is_expired() && cx.replay(cached.ops, cached.ids) {
+                return Ok(cached.markup);
+            }
+        }
+        cx.start_recording();
+        Err(key)
+    }
+
+    pub(crate) fn store(&self, key: String, cx: &mut Context, markup: &Markup) {
+        if let Some(Recording {
+            ops,
+            uncacheable: false,
+            ids,
+        }) = cx.stop_recording()
+        {
+            let cached = CachedRender {
+                markup: markup.clone(),
+                ops,
+                ids,
+                tags: self.tags.clone(),
+                expires: self.max_age.map(|max_age| Instant::now() + max_age),
+            };
+            RENDER_CACHE.read().unwrap().set(key, cached);
+        }
+    }
+
+    fn key(&self, base: &str, cx: &Context) -> String {
+        let mut key = concat_string!(base, "|", self.keys.join("|"));
+        for context in self.contexts.iter() {
+            let value = match context {
+                CacheContext::LangId => cx.langid().to_string(),
+                CacheContext::Theme => cx.theme().single_name().to_owned(),
+                CacheContext::Route => cx
+                    .request()
+                    .match_pattern()
+                    .unwrap_or_else(|| cx.request().path().to_owned()),
+                CacheContext::Roles => session_roles(cx.request()).join(","),
+            };
+            key.push('|');
+            key.push_str(&value);
+        }
+        key
+    }
+}
+
+/// Código renderizado en caché, con los cambios que se hicieron en el contexto al renderizarlo.
+#[derive(Clone)]
+pub struct CachedRender {
+    markup: Markup,
+    ops: Vec<RecordedOp>,
+    ids: (usize, usize),
+    tags: Vec<String>,
+    expires: Option<Instant>,
+}
+
+impl CachedRender {
+    // CachedRender GETTERS.
+
+    pub fn markup(&self) -> &Markup {
+        &self.markup
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| expires <= Instant::now())
+    }
+}
+
+/// Almacén del código renderizado en caché. Por defecto se usa [`MemoryRenderCache`], y se puede
+/// sustituir por otro con [`set_render_cache_store()`].
+pub trait RenderCacheStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<CachedRender>;
+
+    fn set(&self, key: String, cached: CachedRender);
+
+    /// Elimina el código en caché que tenga alguna de las etiquetas `tags`.
+    fn invalidate_tags(&self, tags: &[&str]);
+
+    fn clear(&self);
+}
+
+/// Almacén en memoria del código renderizado en caché. Cuando se llena se descarta el código usado
+/// hace más tiempo.
+pub struct MemoryRenderCache {
+    max_entries: usize,
+    entries: RwLock<HashMap<String, (CachedRender, u64)>>,
+    // Contador para ordenar las entradas por su último uso.
+    uses: AtomicU64,
+}
+
+impl Default for MemoryRenderCache {
+    fn default() -> Self {
+        MemoryRenderCache::new(1000)
+    }
+}
+
+impl MemoryRenderCache {
+    /// Almacén que guarda como máximo `max_entries` códigos renderizados.
+    pub fn new(max_entries: usize) -> Self {
+        MemoryRenderCache {
+            max_entries: max_entries.max(1),
+            entries: RwLock::new(HashMap::new()),
+            uses: AtomicU64::new(0),
+        }
+    }
+
+    fn next_use(&self) -> u64 {
+        self.uses.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl RenderCacheStore for MemoryRenderCache {
+    fn get(&self, key: &str) -> Option<CachedRender> {
+        let mut entries = self.entries.write().unwrap();
+        let (cached, last_use) = entries.get_mut(key)?;
+        *last_use = self.next_use();
+        Some(cached.clone())
+    }
+
+    fn set(&self, key: String, cached: CachedRender) {
+        let mut entries = self.entries.write().unwrap();
+        entries.retain(|_, (cached, _)| !cached.is_expired());
+        if !entries.contains_key(&key) && entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (_, last_use))| *last_use)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, (cached, self.next_use()));
+    }
+
+    fn invalidate_tags(&self, tags: &[&str]) {
+        self.entries
+            .write()
+            .unwrap()
+            .retain(|_, (cached, _)| !cached.tags.iter().any(|tag| tags.contains(&tag.as_str())));
+    }
+
+    fn clear(&self) {
+        self.entries.write().unwrap().clear();
+    }
+}
+
+static RENDER_CACHE: LazyStatic<RwLock<Arc<dyn RenderCacheStore>>> =
+    LazyStatic::new(|| RwLock::new(Arc::new(MemoryRenderCache::default())));
+
+/// Sustituye el almacén del código renderizado en caché, normalmente al inicializar un módulo.
+pub fn set_render_cache_store(store: impl RenderCacheStore + 'static) {
+    *RENDER_CACHE.write().unwrap() = Arc::new(store);
+}
+
+/// Invalida el código en caché que tenga alguna de las etiquetas `tags`.
+pub fn invalidate_cache_tags(tags: &[&str]) {
+    RENDER_CACHE.read().unwrap().invalidate_tags(tags);
+}
+
+/// Vacía la caché del código renderizado.
+pub fn clear_render_cache() {
+    RENDER_CACHE.read().unwrap().clear();
+}
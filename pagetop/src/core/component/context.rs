@@ -1,14 +1,20 @@
 use crate::base::component::add_base_assets;
 use crate::core::theme::all::{theme_by_single_name, THEME};
 use crate::core::theme::ThemeRef;
-use crate::html::{html, Assets, HeadScript, HeadStyles, JavaScript, Markup, StyleSheet};
+use crate::html::{
+    html, Assets, AssetsTrait, HeadScript, HeadStyles, JavaScript, Markup, StyleSheet,
+};
 use crate::locale::{LanguageIdentifier, LANGID};
-use crate::service::HttpRequest;
+use crate::service::{csrf_token, HttpRequest, RequestId};
 use crate::{concat_string, util};
 
+use rand::distributions::Alphanumeric;
+use rand::{rngs::OsRng, Rng};
+
 use std::collections::HashMap;
 use std::str::FromStr;
 
+#[derive(Clone)]
 pub enum ContextOp {
     LangId(&'static LanguageIdentifier),
     Theme(&'static str),
@@ -28,6 +34,22 @@ pub enum ContextOp {
     AddBaseAssets,
 }
 
+// Cambio en el contexto que se repite al usar el código renderizado en caché.
+#[derive(Clone)]
+pub(crate) enum RecordedOp {
+    Alter(ContextOp),
+    SetParam(&'static str, String),
+    RemoveParam(&'static str),
+}
+
+// Cambios en el contexto mientras se renderiza código para guardarlo en caché.
+pub(crate) struct Recording {
+    pub ops: Vec<RecordedOp>,
+    pub uncacheable: bool,
+    // Valores del contador de identificadores al empezar y al terminar.
+    pub ids: (usize, usize),
+}
+
 #[rustfmt::skip]
 pub struct Context {
     request   : HttpRequest,
@@ -39,12 +61,23 @@ pub struct Context {
     headscript: Assets<HeadScript>,                     // Scripts in head.
     params    : HashMap<&'static str, String>,
     id_counter: usize,
+    nonce     : String,                                 // Nonce for inline scripts and styles.
+    request_id: Option<RequestId>,
+    recorders : Vec<Recording>,                         // Changes while caching.
+}
+
+/// Fuentes de los *scripts* y estilos de una página para la política de seguridad del contenido.
+#[derive(Clone, Default)]
+pub(crate) struct ContentSources {
+    pub script_src: Vec<String>,
+    pub style_src: Vec<String>,
 }
 
 impl Context {
     #[rustfmt::skip]
     pub(crate) fn new(request: HttpRequest) -> Self {
         Context {
+            request_id: RequestId::of(&request),
             request,
             langid    : &LANGID,
             theme     : *THEME,
@@ -54,11 +87,18 @@ impl Context {
             headscript: Assets::<HeadScript>::new(),    // Scripts in head.
             params    : HashMap::<&str, String>::new(),
             id_counter: 0,
+            nonce     : (0..22).map(|_| OsRng.sample(Alphanumeric) as char).collect(),
+            recorders : Vec::new(),
         }
     }
 
     #[rustfmt::skip]
     pub fn alter(&mut self, op: ContextOp) -> &mut Self {
+        // Assets are recorded to be replayed when the cached render is used.
+        if !matches!(op, ContextOp::LangId(_) | ContextOp::Theme(_)) {
+            self.record(RecordedOp::Alter(op.clone()));
+        }
+
         match op {
             ContextOp::LangId(langid) => {
                 self.langid = langid;
@@ -71,13 +111,17 @@ impl Context {
             ContextOp::AddStyleSheet(css)     => { self.stylesheet.add(css);     }
             ContextOp::RemoveStyleSheet(path) => { self.stylesheet.remove(path); }
             // Styles in head.
-            ContextOp::AddHeadStyles(styles)  => { self.headstyles.add(styles);  }
+            ContextOp::AddHeadStyles(styles)  => {
+                self.headstyles.add(styles.with_nonce(&self.nonce));
+            }
             ContextOp::RemoveHeadStyles(path) => { self.headstyles.remove(path); }
             // JavaScripts.
             ContextOp::AddJavaScript(js)      => { self.javascript.add(js);      }
             ContextOp::RemoveJavaScript(path) => { self.javascript.remove(path); }
             // Scripts in head.
-            ContextOp::AddHeadScript(script)  => { self.headscript.add(script);  }
+            ContextOp::AddHeadScript(script)  => {
+                self.headscript.add(script.with_nonce(&self.nonce));
+            }
             ContextOp::RemoveHeadScript(path) => { self.headscript.remove(path); }
 
             // Add assets to properly use base components.
@@ -87,15 +131,27 @@ impl Context {
     }
 
     pub fn set_param<T: FromStr + ToString>(&mut self, key: &'static str, value: T) -> &mut Self {
-        self.params.insert(key, value.to_string());
+        let value = value.to_string();
+        self.record(RecordedOp::SetParam(key, value.clone()));
+        self.params.insert(key, value);
         self
     }
 
     pub fn remove_param(&mut self, key: &'static str) -> &mut Self {
+        self.record(RecordedOp::RemoveParam(key));
         self.params.remove(key);
         self
     }
 
+    /// Impide guardar en caché el código que se está renderizando, porque incluye datos propios de
+    /// la sesión o de la petición. Ver [`RenderCache`](crate::core::component::RenderCache).
+    pub fn set_uncacheable(&mut self) -> &mut Self {
+        for recording in self.recorders.iter_mut() {
+            recording.uncacheable = true;
+        }
+        self
+    }
+
     /// Context GETTERS.
 
     pub fn request(&self) -> &HttpRequest {
@@ -119,6 +175,25 @@ impl Context {
         None
     }
 
+    /// Valor aleatorio de un solo uso para autorizar los *scripts* y estilos en línea de la página
+    /// en la política de seguridad del contenido.
+    pub fn nonce(&self) -> &str {
+        self.nonce.as_str()
+    }
+
+    /// Token anti-falsificación de la sesión para incluirlo en los formularios. El código que lo
+    /// incluye no se guarda en caché. Ver [`csrf_token()`](crate::service::csrf_token).
+    pub fn csrf_token(&mut self) -> String {
+        self.set_uncacheable();
+        csrf_token(&self.request)
+    }
+
+    /// Identificador de la petición, para incluirlo en la página si es útil, por ejemplo, en los
+    /// mensajes de error.
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_ref().map(RequestId::as_str)
+    }
+
     /// Context PREPARE.
 
     pub fn prepare(&mut self) -> Markup {
@@ -130,6 +205,71 @@ impl Context {
         }
     }
 
+    pub(crate) fn content_sources(&self) -> ContentSources {
+        let nonce = concat_string!("'nonce-", self.nonce, "'");
+
+        let mut sources = ContentSources::default();
+        for js in self.javascript.iter() {
+            add_source(&mut sources.script_src, source_of(js.path()));
+        }
+        if self.headscript.iter().next().is_some() {
+            add_source(&mut sources.script_src, nonce.clone());
+        }
+        for css in self.stylesheet.iter() {
+            add_source(&mut sources.style_src, source_of(css.path()));
+        }
+        if self.headstyles.iter().next().is_some() {
+            add_source(&mut sources.style_src, nonce);
+        }
+        sources
+    }
+
+    // Context CACHE.
+
+    pub(crate) fn start_recording(&mut self) {
+        self.recorders.push(Recording {
+            ops: Vec::new(),
+            uncacheable: false,
+            ids: (self.id_counter, self.id_counter),
+        });
+    }
+
+    pub(crate) fn stop_recording(&mut self) -> Option<Recording> {
+        let mut recording = self.recorders.pop()?;
+        recording.ids.1 = self.id_counter;
+        Some(recording)
+    }
+
+    // Repite los cambios del código en caché, y avanza el contador de identificadores para no
+    // repetir los que incluye. Devuelve `false` si ya se han generado identificadores que podrían
+    // coincidir con los del código en caché.
+    pub(crate) fn replay(&mut self, ops: Vec<RecordedOp>, ids: (usize, usize)) -> bool {
+        if ids.0 != ids.1 && self.id_counter > ids.0 {
+            return false;
+        }
+        self.id_counter = self.id_counter.max(ids.1);
+        for op in ops.into_iter() {
+            match op {
+                RecordedOp::Alter(op) => {
+                    self.alter(op);
+                }
+                RecordedOp::SetParam(key, value) => {
+                    self.set_param(key, value);
+                }
+                RecordedOp::RemoveParam(key) => {
+                    self.remove_param(key);
+                }
+            }
+        }
+        true
+    }
+
+    fn record(&mut self, op: RecordedOp) {
+        for recording in self.recorders.iter_mut() {
+            recording.ops.push(op.clone());
+        }
+    }
+
     // Context EXTRAS.
 
     pub fn required_id<T>(&mut self, id: Option<String>) -> String {
@@ -151,3 +291,24 @@ impl Context {
         }
     }
 }
+
+fn add_source(sources: &mut Vec<String>, source: String) {
+    if !sources.contains(&source) {
+        sources.push(source);
+    }
+}
+
+// Origen de un recurso para la política de seguridad del contenido: el de las URL absolutas, o
+// 'self' para las rutas del propio sitio.
+fn source_of(path: &str) -> String {
+    for scheme in ["https://", "http://", "//"] {
+        if let Some(rest) = path.strip_prefix(scheme) {
+            let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+            return match scheme {
+                "//" => host.to_owned(),
+                _ => concat_string!(scheme, host),
+            };
+        }
+    }
+    "'self'".to_owned()
+}
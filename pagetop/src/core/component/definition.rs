@@ -1,7 +1,7 @@
 use crate::base::action;
-use crate::core::component::Context;
+use crate::core::component::{Context, RenderCache};
 use crate::html::{html, Markup, PrepareMarkup};
-use crate::{util, HasHandle, Weight};
+use crate::{concat_string, util, HasHandle, Weight};
 
 use std::any::Any;
 
@@ -13,6 +13,18 @@ pub trait ComponentBase: Any {
     fn as_mut_any(&mut self) -> &mut dyn Any;
 }
 
+/// Valores de los campos de un componente que genera [`#[derive(Component)]`](crate::Component).
+///
+/// Los usa [`#[component_trait]`](crate::component_trait) para implementar los métodos `id()`,
+/// `weight()` e `is_renderable()` de [`ComponentTrait`].
+pub trait ComponentFields {
+    fn field_id(&self) -> Option<String>;
+
+    fn field_weight(&self) -> Weight;
+
+    fn field_is_renderable(&self, cx: &Context) -> bool;
+}
+
 pub trait ComponentTrait: ComponentBase + HasHandle + Send + Sync {
     fn new() -> Self
     where
@@ -39,6 +51,11 @@ pub trait ComponentTrait: ComponentBase + HasHandle + Send + Sync {
         true
     }
 
+    /// Configuración de la caché del código renderizado. Por defecto no se guarda en caché.
+    fn render_cache(&self) -> Option<RenderCache> {
+        None
+    }
+
     #[allow(unused_variables)]
     fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
         PrepareMarkup::None
@@ -48,6 +65,23 @@ pub trait ComponentTrait: ComponentBase + HasHandle + Send + Sync {
 impl<C: ComponentTrait> ComponentBase for C {
     fn render(&mut self, cx: &mut Context) -> Markup {
         if self.is_renderable(cx) {
+            // Código renderizado en caché.
+            let cache = self.render_cache();
+            let cache_key = match &cache {
+                Some(cache) => {
+                    let base = concat_string!(
+                        self.handle().to_string(),
+                        ":",
+                        self.id().unwrap_or_default()
+                    );
+                    match cache.lookup(&base, cx) {
+                        Ok(markup) => return markup,
+                        Err(key) => Some(key),
+                    }
+                }
+                None => None,
+            };
+
             // Acciones del tema antes de preparar el componente.
             cx.theme().before_prepare_component(self, cx);
 
@@ -76,6 +110,14 @@ impl<C: ComponentTrait> ComponentBase for C {
                 action::component::AfterPrepareComponent::dispatch(self, cx, Some(id));
             }
 
+            // Acciones de los módulos para alterar o sustituir el código renderizado.
+            let markup = action::component::AlterComponentMarkup::dispatch(self, cx, markup);
+
+            // Guarda en caché el código renderizado.
+            if let (Some(cache), Some(key)) = (cache, cache_key) {
+                cache.store(key, cx, &markup);
+            }
+
             markup
         } else {
             html! {}
@@ -1,4 +1,23 @@
 mod definition;
 pub use definition::{ModuleBase, ModuleRef, ModuleTrait};
 
+mod command;
+pub use command::{CliCommand, FnCliCommand};
+
+mod health;
+pub use health::{Health, HealthStatus};
+
+mod permission;
+pub use permission::Permission;
+
+mod task;
+pub use task::{FnScheduledTask, Schedule, ScheduledTask, TaskRun};
+
+#[cfg(feature = "database")]
+mod status;
+#[cfg(feature = "database")]
+pub use status::ModuleStatus;
+
 pub(crate) mod all;
+pub use all::{dropped_modules, enabled_modules, inactive_modules};
+pub use all::{last_task_run, permissions, scheduled_tasks};
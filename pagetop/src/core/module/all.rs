@@ -1,153 +1,840 @@
-use crate::core::action::add_action;
-use crate::core::module::ModuleRef;
+use crate::app::PrepareError;
+use crate::core::action::{action_handles, add_action};
+use crate::core::module::{Health, ModuleRef, Permission, ScheduledTask, TaskRun};
 use crate::core::theme::all::THEMES;
-use crate::{config, service, service_for_static_files, static_files, trace, LazyStatic};
+use crate::core::theme::component_handles;
+use crate::{config, service, service_for_static_files, static_files, trace, util, LazyStatic};
 
+#[cfg(feature = "database")]
+use crate::core::module::status::{self, ModuleRecord, ModuleStatus};
 #[cfg(feature = "database")]
 use crate::db::*;
 
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::RwLock;
+use std::time::Instant;
 
 static_files!(base);
 
 // MODULES *****************************************************************************************
 
+// Módulos habilitados en el orden resuelto por `register_modules()`. Es el orden que siguen
+// `register_actions()`, `init_modules()`, `configure_services()`, las migraciones y los eventos del
+// ciclo de vida; `shutdown_modules()` usa el orden inverso.
 static ENABLED_MODULES: LazyStatic<RwLock<Vec<ModuleRef>>> =
     LazyStatic::new(|| RwLock::new(Vec::new()));
 
+// Módulos habilitados por la última llamada a `register_modules()`. Sólo de estos se registran las
+// acciones, los permisos y las tareas programadas, y se inicializan, para que preparar de nuevo la
+// aplicación no repita estos pasos con los módulos ya habilitados.
+static NEW_MODULES: LazyStatic<RwLock<Vec<ModuleRef>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
 static DROPPED_MODULES: LazyStatic<RwLock<Vec<ModuleRef>>> =
     LazyStatic::new(|| RwLock::new(Vec::new()));
 
+// Módulos compilados en la aplicación que no están activos por su estado en la base de datos.
+static INACTIVE_MODULES: LazyStatic<RwLock<Vec<ModuleRef>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
+#[cfg(feature = "database")]
+static MODULE_STATUS: LazyStatic<RwLock<HashMap<String, ModuleRecord>>> =
+    LazyStatic::new(|| RwLock::new(HashMap::new()));
+
+/// Devuelve los módulos habilitados en el orden en que se registran sus acciones, se inicializan,
+/// se configuran sus servicios y se aplican sus migraciones.
+///
+/// Cada módulo va siempre después de sus dependencias. Entre los módulos que no dependen entre sí
+/// va antes el de menor [`weight()`](crate::core::module::ModuleTrait::weight) y, a igualdad de
+/// peso, por orden alfabético de nombre, de manera que el orden no depende de cómo se declaren las
+/// dependencias.
+pub fn enabled_modules() -> Vec<ModuleRef> {
+    ENABLED_MODULES.read().unwrap().clone()
+}
+
+/// Devuelve los módulos descartados por otros módulos con
+/// [`drop_modules()`](crate::core::module::ModuleTrait::drop_modules).
+pub fn dropped_modules() -> Vec<ModuleRef> {
+    DROPPED_MODULES.read().unwrap().clone()
+}
+
+/// Devuelve los módulos compilados en la aplicación que no están activos por su estado en la base de
+/// datos.
+pub fn inactive_modules() -> Vec<ModuleRef> {
+    INACTIVE_MODULES.read().unwrap().clone()
+}
+
 // REGISTER MODULES ********************************************************************************
 
-pub fn register_modules(app: ModuleRef) {
+pub fn register_modules(app: ModuleRef) -> Result<(), PrepareError> {
     // List of modules to drop.
     let mut list: Vec<ModuleRef> = Vec::new();
-    add_to_dropped(&mut list, app);
-    DROPPED_MODULES.write().unwrap().append(&mut list);
+    add_to_dropped(&mut list, app, &mut Vec::new())?;
+    append_new(&mut DROPPED_MODULES.write().unwrap(), list);
 
     // List of modules to enable.
     let mut list: Vec<ModuleRef> = Vec::new();
 
     // Enable default themes.
-    add_to_enabled(&mut list, &crate::base::theme::Basic);
-    add_to_enabled(&mut list, &crate::base::theme::Chassis);
-    add_to_enabled(&mut list, &crate::base::theme::Inception);
+    add_to_enabled(&mut list, &crate::base::theme::Basic, &mut Vec::new())?;
+    add_to_enabled(&mut list, &crate::base::theme::Chassis, &mut Vec::new())?;
+    add_to_enabled(&mut list, &crate::base::theme::Inception, &mut Vec::new())?;
 
     // Enable application modules.
-    add_to_enabled(&mut list, app);
+    add_to_enabled(&mut list, app, &mut Vec::new())?;
+
+    let list = sort_modules(list)?;
+    check_versions(&list)?;
 
-    list.reverse();
-    ENABLED_MODULES.write().unwrap().append(&mut list);
+    // Discard modules disabled or uninstalled at runtime.
+    #[cfg(feature = "database")]
+    let list = filter_by_status(list)?;
+
+    // Skip modules already enabled by a previous call.
+    let mut enabled = ENABLED_MODULES.write().unwrap();
+    let mut new_modules: Vec<ModuleRef> = Vec::new();
+    for m in list.into_iter() {
+        match enabled.iter().find(|e| e.handle() == m.handle()) {
+            Some(e) => check_duplicate(*e, m)?,
+            None => new_modules.push(m),
+        }
+    }
+
+    for m in new_modules.iter() {
+        if let Some(theme) = m.theme() {
+            let mut registered_themes = THEMES.write().unwrap();
+            if !registered_themes
+                .iter()
+                .any(|t| t.handle() == theme.handle())
+            {
+                registered_themes.push(theme);
+                trace::debug!("Enabling \"{}\" theme", theme.single_name());
+            }
+        } else {
+            trace::debug!("Enabling \"{}\" module", m.single_name());
+        }
+    }
+    enabled.extend(new_modules.iter());
+    *NEW_MODULES.write().unwrap() = new_modules;
+    Ok(())
+}
+
+// Añade a `modules` los módulos de `list` que aún no están.
+fn append_new(modules: &mut Vec<ModuleRef>, list: Vec<ModuleRef>) {
+    for m in list.into_iter() {
+        if !modules.iter().any(|r| r.handle() == m.handle()) {
+            modules.push(m);
+        }
+    }
 }
 
-fn add_to_dropped(list: &mut Vec<ModuleRef>, module: ModuleRef) {
+fn add_to_dropped(
+    list: &mut Vec<ModuleRef>,
+    module: ModuleRef,
+    chain: &mut Vec<ModuleRef>,
+) -> Result<(), PrepareError> {
+    check_cycle(module, chain)?;
     for d in module.drop_modules().iter() {
         if !list.iter().any(|m| m.handle() == d.handle()) {
             list.push(*d);
             trace::debug!("Module \"{}\" dropped", d.single_name());
         }
     }
+    chain.push(module);
     for d in module.dependencies().iter() {
-        add_to_dropped(list, *d);
+        add_to_dropped(list, *d, chain)?;
     }
+    chain.pop();
+    Ok(())
 }
 
-fn add_to_enabled(list: &mut Vec<ModuleRef>, module: ModuleRef) {
-    if !list.iter().any(|m| m.handle() == module.handle()) {
-        if DROPPED_MODULES
-            .read()
-            .unwrap()
+fn add_to_enabled(
+    list: &mut Vec<ModuleRef>,
+    module: ModuleRef,
+    chain: &mut Vec<ModuleRef>,
+) -> Result<(), PrepareError> {
+    check_cycle(module, chain)?;
+    if let Some(m) = list.iter().find(|m| m.handle() == module.handle()) {
+        return check_duplicate(*m, module);
+    }
+    if DROPPED_MODULES
+        .read()
+        .unwrap()
+        .iter()
+        .any(|m| m.handle() == module.handle())
+    {
+        return Err(PrepareError::DroppedDependency {
+            module: module.single_name(),
+            chain: chain_names(chain, module),
+        });
+    }
+
+    list.push(module);
+
+    chain.push(module);
+    for d in module.dependencies().iter() {
+        add_to_enabled(list, *d, chain)?;
+    }
+    chain.pop();
+    Ok(())
+}
+
+// Ordena los módulos (orden topológico) de manera que cada uno quede después de sus dependencias.
+// Entre los que pueden ir a continuación se elige el de menor peso y, a igualdad de peso, el primero
+// por orden alfabético de nombre.
+fn sort_modules(mut pending: Vec<ModuleRef>) -> Result<Vec<ModuleRef>, PrepareError> {
+    let mut sorted: Vec<ModuleRef> = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let next = pending
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| {
+                m.dependencies()
+                    .iter()
+                    .all(|d| sorted.iter().any(|s| s.handle() == d.handle()))
+            })
+            .min_by_key(|(_, m)| (m.weight(), m.single_name()))
+            .map(|(index, _)| index);
+        match next {
+            Some(index) => sorted.push(pending.remove(index)),
+            // Sólo si quedan módulos que dependen unos de otros en un ciclo.
+            None => {
+                return Err(PrepareError::DependencyCycle {
+                    chain: pending.iter().map(|m| m.single_name()).collect(),
+                })
+            }
+        }
+    }
+    Ok(sorted)
+}
+
+// Separa los módulos deshabilitados o desinstalados, y los que dependen de ellos, según su estado
+// en la base de datos.
+#[cfg(feature = "database")]
+fn filter_by_status(list: Vec<ModuleRef>) -> Result<Vec<ModuleRef>, PrepareError> {
+    if DBCONN.get().is_none() {
+        return Ok(list);
+    }
+    let module_status = run_now(status::load())?;
+
+    let mut active: Vec<ModuleRef> = Vec::new();
+    let mut inactive: Vec<ModuleRef> = Vec::new();
+    for m in list.into_iter() {
+        if let Some(s) = module_status
+            .get(m.single_name())
+            .map(|r| r.status)
+            .filter(|s| *s != ModuleStatus::Installed)
+        {
+            trace::info!("Module \"{}\" is {}", m.single_name(), s);
+            inactive.push(m);
+        } else if let Some(d) = m
+            .dependencies()
             .iter()
-            .any(|m| m.handle() == module.handle())
+            .find(|d| inactive.iter().any(|i| i.handle() == d.handle()))
         {
-            panic!(
-                "Trying to enable \"{}\" module which is dropped",
-                module.single_name()
+            trace::warn!(
+                "Module \"{}\" is inactive because it depends on \"{}\"",
+                m.single_name(),
+                d.single_name()
             );
+            inactive.push(m);
         } else {
-            list.push(module);
+            active.push(m);
+        }
+    }
+    *MODULE_STATUS.write().unwrap() = module_status;
+    append_new(&mut INACTIVE_MODULES.write().unwrap(), inactive);
+    Ok(active)
+}
 
-            let mut dependencies = module.dependencies();
-            dependencies.reverse();
-            for d in dependencies.iter() {
-                add_to_enabled(list, *d);
+// Comprueba que la versión de cada módulo es válida y compatible con la versión de PageTop.
+fn check_versions(list: &[ModuleRef]) -> Result<(), PrepareError> {
+    let pagetop = env!("CARGO_PKG_VERSION");
+    let pagetop_version =
+        semver::Version::parse(pagetop).map_err(|_| PrepareError::InvalidVersion {
+            module: "PageTop",
+            version: pagetop,
+        })?;
+    for m in list.iter() {
+        if let Some(version) = m.version() {
+            if semver::Version::parse(version).is_err() {
+                return Err(PrepareError::InvalidVersion {
+                    module: m.single_name(),
+                    version,
+                });
             }
-
-            if let Some(theme) = module.theme() {
-                let mut registered_themes = THEMES.write().unwrap();
-                if !registered_themes
-                    .iter()
-                    .any(|t| t.handle() == theme.handle())
-                {
-                    registered_themes.push(theme);
-                    trace::debug!("Enabling \"{}\" theme", theme.single_name());
-                }
-            } else {
-                trace::debug!("Enabling \"{}\" module", module.single_name());
+        }
+        let requires = semver::VersionReq::parse(m.requires_pagetop()).map_err(|_| {
+            PrepareError::InvalidVersion {
+                module: m.single_name(),
+                version: m.requires_pagetop(),
             }
+        })?;
+        if !requires.matches(&pagetop_version) {
+            return Err(PrepareError::IncompatibleModule {
+                module: m.single_name(),
+                requires: m.requires_pagetop(),
+                pagetop,
+            });
         }
     }
+    Ok(())
+}
+
+fn check_cycle(module: ModuleRef, chain: &[ModuleRef]) -> Result<(), PrepareError> {
+    match chain.iter().find(|m| m.handle() == module.handle()) {
+        Some(m) => {
+            check_duplicate(*m, module)?;
+            Err(PrepareError::DependencyCycle {
+                chain: chain_names(chain, module),
+            })
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_duplicate(registered: ModuleRef, module: ModuleRef) -> Result<(), PrepareError> {
+    if registered.type_name() != module.type_name() {
+        return Err(PrepareError::HandleCollision {
+            handle: module.handle(),
+            element: module.type_name(),
+            other: registered.type_name(),
+        });
+    }
+    Ok(())
+}
+
+fn chain_names(chain: &[ModuleRef], module: ModuleRef) -> Vec<&'static str> {
+    chain
+        .iter()
+        .chain([module].iter())
+        .map(|m| m.single_name())
+        .collect()
 }
 
 // REGISTER ACTIONS ********************************************************************************
 
 pub fn register_actions() {
-    for m in ENABLED_MODULES.read().unwrap().iter() {
+    for m in NEW_MODULES.read().unwrap().iter() {
         for a in m.actions().into_iter() {
-            add_action(a);
+            add_action(m.single_name(), a);
+        }
+    }
+}
+
+// REGISTER PERMISSIONS ****************************************************************************
+
+// Permisos declarados por los módulos habilitados, con el nombre del módulo que los define.
+static PERMISSIONS: LazyStatic<RwLock<Vec<(&'static str, Permission)>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
+pub fn register_permissions() {
+    let mut permissions = PERMISSIONS.write().unwrap();
+    for m in NEW_MODULES.read().unwrap().iter() {
+        for p in m.permissions().into_iter() {
+            match permissions.iter().find(|(_, r)| r.name() == p.name()) {
+                Some((module, _)) => trace::warn!(
+                    "Permission \"{}\" of module \"{}\" ignored, already defined by \"{}\"",
+                    p.name(),
+                    m.single_name(),
+                    module
+                ),
+                None => permissions.push((m.single_name(), p)),
+            }
         }
     }
 }
 
+/// Devuelve los permisos definidos por los módulos habilitados, con el nombre del módulo que define
+/// cada uno.
+pub fn permissions() -> Vec<(&'static str, Permission)> {
+    PERMISSIONS.read().unwrap().clone()
+}
+
+pub(crate) fn is_permission(name: &str) -> bool {
+    PERMISSIONS
+        .read()
+        .unwrap()
+        .iter()
+        .any(|(_, p)| p.name() == name)
+}
+
 // INIT MODULES ************************************************************************************
 
 pub fn init_modules() {
     trace::info!("Calling application bootstrap");
-    for m in ENABLED_MODULES.read().unwrap().iter() {
+    for m in NEW_MODULES.read().unwrap().iter() {
         m.init();
     }
 }
 
+// CHECK HANDLES ***********************************************************************************
+
+// Comprueba que no hay dos tipos distintos con el mismo identificador entre los módulos y temas
+// habilitados, los componentes añadidos a las regiones y las acciones registradas, y guarda el
+// tipo de cada identificador para consultarlo con `util::handle_name()`.
+pub fn check_handles() -> Result<(), PrepareError> {
+    let mut handles = Vec::new();
+    for m in ENABLED_MODULES.read().unwrap().iter() {
+        handles.push((m.handle(), m.type_name()));
+    }
+    for t in THEMES.read().unwrap().iter() {
+        handles.push((t.handle(), t.type_name()));
+    }
+    handles.extend(component_handles());
+    handles.extend(action_handles());
+
+    for (handle, type_name) in handles.into_iter() {
+        if let Err(other) = util::register_handle_name(handle, type_name) {
+            return Err(PrepareError::HandleCollision {
+                handle,
+                element: type_name,
+                other,
+            });
+        }
+    }
+    trace::debug!("Checked handles of the registered elements");
+    Ok(())
+}
+
+// LIFECYCLE HOOKS *********************************************************************************
+
+pub async fn server_started(addrs: Vec<SocketAddr>) {
+    for m in enabled_modules().iter() {
+        m.on_server_started(&addrs).await;
+    }
+}
+
+pub async fn shutdown_modules() {
+    trace::info!("Shutting down modules");
+    for m in enabled_modules().iter().rev() {
+        m.on_shutdown().await;
+    }
+}
+
+// HEALTH CHECKS ***********************************************************************************
+
+pub async fn health_checks() -> Vec<(&'static str, Health)> {
+    let mut checks = Vec::new();
+    for m in enabled_modules().iter() {
+        if let Some(health) = m.health_check().await {
+            checks.push((m.single_name(), health));
+        }
+    }
+    checks
+}
+
+// SCHEDULED TASKS *********************************************************************************
+
+// Tareas programadas de los módulos habilitados, con el nombre del módulo que las define.
+static SCHEDULED_TASKS: LazyStatic<RwLock<Vec<(&'static str, ScheduledTask)>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
+// Última ejecución de cada tarea, por módulo y nombre de la tarea.
+static TASK_RUNS: LazyStatic<RwLock<HashMap<(&'static str, &'static str), TaskRun>>> =
+    LazyStatic::new(|| RwLock::new(HashMap::new()));
+
+pub fn register_scheduled_tasks() {
+    let mut tasks = SCHEDULED_TASKS.write().unwrap();
+    for m in NEW_MODULES.read().unwrap().iter() {
+        for t in m.scheduled_tasks().into_iter() {
+            if let Err(e) = t.schedule().validate() {
+                trace::warn!(
+                    "Scheduled task \"{}\" of module \"{}\" ignored ({})",
+                    t.name(),
+                    m.single_name(),
+                    e
+                );
+            } else if tasks
+                .iter()
+                .any(|(module, r)| *module == m.single_name() && r.name() == t.name())
+            {
+                trace::warn!(
+                    "Scheduled task \"{}\" of module \"{}\" ignored, it is already defined",
+                    t.name(),
+                    m.single_name()
+                );
+            } else {
+                tasks.push((m.single_name(), t));
+            }
+        }
+    }
+}
+
+/// Devuelve las tareas programadas de los módulos habilitados, con el nombre del módulo que define
+/// cada una.
+pub fn scheduled_tasks() -> Vec<(&'static str, ScheduledTask)> {
+    SCHEDULED_TASKS.read().unwrap().clone()
+}
+
+/// Devuelve el resultado de la última ejecución de la tarea `task` del módulo `module` en este
+/// proceso, si se ha ejecutado.
+pub fn last_task_run(module: &str, task: &str) -> Option<TaskRun> {
+    TASK_RUNS
+        .read()
+        .unwrap()
+        .iter()
+        .find(|((m, t), _)| *m == module && *t == task)
+        .map(|(_, run)| run.clone())
+}
+
+/// Ejecuta la tarea, registra en las trazas y guarda el momento, la duración y el resultado.
+pub fn run_scheduled_task(module: &'static str, task: &ScheduledTask) -> TaskRun {
+    trace::debug!(
+        "Running scheduled task \"{}\" of module \"{}\"",
+        task.name(),
+        module
+    );
+    let started = crate::datetime::Utc::now();
+    let timer = Instant::now();
+    let outcome = task.run();
+    let run = TaskRun::new(started, timer.elapsed(), outcome);
+    match run.outcome() {
+        Ok(()) => trace::info!(
+            "Scheduled task \"{}\" of module \"{}\" completed in {:?}",
+            task.name(),
+            module,
+            run.duration()
+        ),
+        Err(e) => trace::error!(
+            "Scheduled task \"{}\" of module \"{}\" failed after {:?} ({})",
+            task.name(),
+            module,
+            run.duration(),
+            e
+        ),
+    }
+    TASK_RUNS
+        .write()
+        .unwrap()
+        .insert((module, task.name()), run.clone());
+    run
+}
+
+// Ejecuta cada tarea según su programación en el sistema de actix-web, salvo que se desactive en la
+// configuración. Las tareas se ejecutan en hilos aparte para no bloquear el servidor.
+pub fn spawn_scheduled_tasks() {
+    if !config::SETTINGS.server.scheduled_tasks {
+        return;
+    }
+    for (module, task) in scheduled_tasks().into_iter() {
+        service::rt::spawn(async move {
+            while let Some(delay) = task.schedule().next_delay() {
+                service::rt::time::sleep(delay).await;
+                let running = service::rt::task::spawn_blocking(move || {
+                    run_scheduled_task(module, &task);
+                });
+                if let Err(e) = running.await {
+                    trace::error!(
+                        "Scheduled task \"{}\" of module \"{}\" panicked ({})",
+                        task.name(),
+                        module,
+                        e
+                    );
+                }
+            }
+        });
+    }
+}
+
 // RUN MIGRATIONS **********************************************************************************
 
+#[cfg(feature = "database")]
+struct EnabledMigrator;
+
+#[cfg(feature = "database")]
+impl MigratorTrait for EnabledMigrator {
+    fn migrations() -> Vec<MigrationItem> {
+        let mut migrations = vec![];
+        for m in ENABLED_MODULES.read().unwrap().iter() {
+            migrations.append(&mut m.migrations());
+        }
+        migrations
+    }
+}
+
+#[cfg(feature = "database")]
+struct DroppedMigrator;
+
+#[cfg(feature = "database")]
+impl MigratorTrait for DroppedMigrator {
+    fn migrations() -> Vec<MigrationItem> {
+        let mut migrations = vec![];
+        for m in DROPPED_MODULES.read().unwrap().iter() {
+            migrations.append(&mut m.migrations());
+        }
+        migrations
+    }
+}
+
 #[cfg(feature = "database")]
 pub fn run_migrations() {
-    if let Some(dbconn) = &*DBCONN {
-        if let Err(e) = run_now({
-            struct Migrator;
-            impl MigratorTrait for Migrator {
-                fn migrations() -> Vec<MigrationItem> {
-                    let mut migrations = vec![];
-                    for m in ENABLED_MODULES.read().unwrap().iter() {
-                        migrations.append(&mut m.migrations());
-                    }
-                    migrations
-                }
-            }
-            Migrator::up(SchemaManagerConnection::Connection(dbconn), None)
-        }) {
-            trace::error!("Database upgrade failed ({})", e);
+    if let Some(dbconn) = DBCONN.get() {
+        match run_now(EnabledMigrator::up(
+            SchemaManagerConnection::Connection(dbconn),
+            None,
+        )) {
+            Ok(_) => record_versions(),
+            Err(e) => trace::error!("Database upgrade failed ({})", e),
         };
 
-        if let Err(e) = run_now({
-            struct Migrator;
-            impl MigratorTrait for Migrator {
-                fn migrations() -> Vec<MigrationItem> {
-                    let mut migrations = vec![];
-                    for m in DROPPED_MODULES.read().unwrap().iter() {
-                        migrations.append(&mut m.migrations());
-                    }
-                    migrations
-                }
-            }
-            Migrator::down(SchemaManagerConnection::Connection(dbconn), None)
-        }) {
+        if let Err(e) = run_now(DroppedMigrator::down(
+            SchemaManagerConnection::Connection(dbconn),
+            None,
+        )) {
             trace::error!("Database downgrade failed ({})", e);
         };
     }
 }
 
+#[cfg(feature = "database")]
+/// Aplica las migraciones pendientes de los módulos habilitados (todas si `steps` es `None`).
+pub fn migrate_up(steps: Option<u32>) -> Result<(), DbErr> {
+    run_now(EnabledMigrator::up(
+        SchemaManagerConnection::Connection(dbconn()?),
+        steps,
+    ))
+}
+
+#[cfg(feature = "database")]
+/// Deshace las últimas migraciones aplicadas de los módulos habilitados (todas si `steps` es
+/// `None`).
+pub fn migrate_down(steps: Option<u32>) -> Result<(), DbErr> {
+    run_now(EnabledMigrator::down(
+        SchemaManagerConnection::Connection(dbconn()?),
+        steps,
+    ))
+}
+
+#[cfg(feature = "database")]
+/// Elimina todas las tablas de la base de datos y vuelve a aplicar todas las migraciones de los
+/// módulos habilitados.
+pub fn migrate_fresh() -> Result<(), DbErr> {
+    run_now(EnabledMigrator::fresh(SchemaManagerConnection::Connection(
+        dbconn()?,
+    )))
+}
+
+#[cfg(feature = "database")]
+/// Devuelve el nombre y el estado (*Applied* o *Pending*) de las migraciones de los módulos
+/// habilitados.
+pub fn migrations_status() -> Result<Vec<(String, String)>, DbErr> {
+    let dbconn = dbconn()?;
+    run_now(EnabledMigrator::install(dbconn))?;
+    Ok(run_now(EnabledMigrator::get_migration_with_status(dbconn))?
+        .iter()
+        .map(|m| (m.name().to_owned(), m.status().to_string()))
+        .collect())
+}
+
+#[cfg(feature = "database")]
+/// Devuelve el número de migraciones pendientes de aplicar de los módulos habilitados.
+pub async fn pending_migrations() -> Result<usize, DbErr> {
+    Ok(EnabledMigrator::get_pending_migrations(dbconn()?)
+        .await?
+        .len())
+}
+
+// MODULE STATUS ***********************************************************************************
+
+// Migraciones de un solo módulo, el que se está instalando o desinstalando.
+#[cfg(feature = "database")]
+static MIGRATING: LazyStatic<RwLock<Option<ModuleRef>>> = LazyStatic::new(|| RwLock::new(None));
+
+#[cfg(feature = "database")]
+struct ModuleMigrator;
+
+#[cfg(feature = "database")]
+impl MigratorTrait for ModuleMigrator {
+    fn migrations() -> Vec<MigrationItem> {
+        match *MIGRATING.read().unwrap() {
+            Some(m) => m.migrations(),
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(feature = "database")]
+/// Devuelve el estado guardado del módulo, o `None` si aún no se ha instalado.
+pub fn module_status(name: &str) -> Option<ModuleStatus> {
+    MODULE_STATUS.read().unwrap().get(name).map(|r| r.status)
+}
+
+#[cfg(feature = "database")]
+/// Devuelve la versión registrada del módulo, o `None` si aún no se ha instalado.
+pub fn module_version(name: &str) -> Option<String> {
+    MODULE_STATUS
+        .read()
+        .unwrap()
+        .get(name)
+        .map(|r| r.version.to_owned())
+}
+
+// Guarda como instalados los módulos activos que aún no tienen estado, y actualiza los módulos
+// cuya versión registrada es distinta de la compilada. Los módulos sin versión, o que no la tenían
+// registrada, no se actualizan.
+#[cfg(feature = "database")]
+fn record_versions() {
+    for m in enabled_modules().iter() {
+        let current = m.version().unwrap_or_default();
+        let result = match module_version(m.single_name()) {
+            None => save_status(*m, ModuleStatus::Installed, current),
+            Some(version) if version == current => Ok(()),
+            Some(version) if version.is_empty() || current.is_empty() => {
+                save_status(*m, ModuleStatus::Installed, current)
+            }
+            Some(version) => {
+                trace::info!(
+                    "Updating \"{}\" module from version {} to {}",
+                    m.single_name(),
+                    version,
+                    current
+                );
+                run_now(m.update(&version))
+                    .and_then(|_| save_status(*m, ModuleStatus::Installed, current))
+            }
+        };
+        if let Err(e) = result {
+            trace::error!("Failed to update \"{}\" module ({})", m.single_name(), e);
+        }
+    }
+}
+
+#[cfg(feature = "database")]
+/// Instala un módulo desinstalado, aplicando sus migraciones, o activa un módulo deshabilitado. Los
+/// cambios se aplican al volver a iniciar la aplicación.
+pub fn install_module(name: &str) -> Result<(), DbErr> {
+    let module = find_module(name)?;
+    for d in module.dependencies().iter() {
+        if !matches!(
+            module_status(d.single_name()),
+            None | Some(ModuleStatus::Installed)
+        ) {
+            return Err(DbErr::Custom(format!(
+                "Module \"{}\" depends on \"{}\", install it first",
+                module.single_name(),
+                d.single_name()
+            )));
+        }
+    }
+    // Al volver a activar un módulo deshabilitado se mantiene la versión registrada, para
+    // actualizarlo en el siguiente inicio si es necesario.
+    let version = match module_status(name) {
+        Some(ModuleStatus::Disabled) => module_version(name),
+        _ => None,
+    };
+    run_module_migrations(module, true)?;
+    save_status(
+        module,
+        ModuleStatus::Installed,
+        version
+            .as_deref()
+            .unwrap_or(module.version().unwrap_or_default()),
+    )
+}
+
+#[cfg(feature = "database")]
+/// Deshabilita un módulo conservando sus datos. Los cambios se aplican al volver a iniciar la
+/// aplicación.
+pub fn disable_module(name: &str) -> Result<(), DbErr> {
+    let module = find_module(name)?;
+    check_dependents(module, &[ModuleStatus::Installed])?;
+    let version = module_version(name);
+    save_status(
+        module,
+        ModuleStatus::Disabled,
+        version
+            .as_deref()
+            .unwrap_or(module.version().unwrap_or_default()),
+    )
+}
+
+#[cfg(feature = "database")]
+/// Desinstala un módulo deshaciendo sus migraciones. Los cambios se aplican al volver a iniciar la
+/// aplicación.
+pub fn uninstall_module(name: &str) -> Result<(), DbErr> {
+    let module = find_module(name)?;
+    check_dependents(module, &[ModuleStatus::Installed, ModuleStatus::Disabled])?;
+    run_module_migrations(module, false)?;
+    save_status(
+        module,
+        ModuleStatus::Uninstalled,
+        module.version().unwrap_or_default(),
+    )
+}
+
+#[cfg(feature = "database")]
+fn find_module(name: &str) -> Result<ModuleRef, DbErr> {
+    enabled_modules()
+        .into_iter()
+        .chain(inactive_modules())
+        .find(|m| m.single_name() == name)
+        .ok_or_else(|| DbErr::Custom(format!("Module \"{}\" not found", name)))
+}
+
+// Comprueba que ningún otro módulo con alguno de los estados dados depende del módulo.
+#[cfg(feature = "database")]
+fn check_dependents(module: ModuleRef, blocking: &[ModuleStatus]) -> Result<(), DbErr> {
+    for m in enabled_modules().into_iter().chain(inactive_modules()) {
+        let status = module_status(m.single_name()).unwrap_or(ModuleStatus::Installed);
+        if blocking.contains(&status)
+            && m.dependencies()
+                .iter()
+                .any(|d| d.handle() == module.handle())
+        {
+            return Err(DbErr::Custom(format!(
+                "Module \"{}\" is required by \"{}\"",
+                module.single_name(),
+                m.single_name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "database")]
+fn run_module_migrations(module: ModuleRef, up: bool) -> Result<(), DbErr> {
+    let dbconn = dbconn()?;
+    *MIGRATING.write().unwrap() = Some(module);
+    let result = match up {
+        true => run_now(ModuleMigrator::up(
+            SchemaManagerConnection::Connection(dbconn),
+            None,
+        )),
+        false => run_now(ModuleMigrator::down(
+            SchemaManagerConnection::Connection(dbconn),
+            None,
+        )),
+    };
+    *MIGRATING.write().unwrap() = None;
+    result
+}
+
+#[cfg(feature = "database")]
+fn save_status(module: ModuleRef, new_status: ModuleStatus, version: &str) -> Result<(), DbErr> {
+    let record = ModuleRecord {
+        status: new_status,
+        version: version.to_owned(),
+    };
+    run_now(status::save(module.single_name(), &record))?;
+    MODULE_STATUS
+        .write()
+        .unwrap()
+        .insert(module.single_name().to_owned(), record);
+    Ok(())
+}
+
+#[cfg(feature = "database")]
+fn dbconn() -> Result<&'static DbConn, DbErr> {
+    match DBCONN.get() {
+        Some(dbconn) => Ok(dbconn),
+        None => Err(DbErr::Conn(RuntimeErr::Internal(
+            DBCONN_NOT_INITIALIZED.to_owned(),
+        ))),
+    }
+}
+
 // CONFIGURE SERVICES ******************************************************************************
 
 pub fn configure_services(scfg: &mut service::web::ServiceConfig) {
@@ -157,6 +844,8 @@ pub fn configure_services(scfg: &mut service::web::ServiceConfig) {
         [&config::SETTINGS.dev.pagetop_project_dir, "pagetop/static/base"]
     );
     for m in ENABLED_MODULES.read().unwrap().iter() {
+        service::set_configuring_module(Some(m.handle()));
         m.configure_service(scfg);
     }
+    service::set_configuring_module(None);
 }
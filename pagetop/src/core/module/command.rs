@@ -0,0 +1,48 @@
+use std::io::Error;
+
+/// Función que ejecuta un subcomando con los argumentos que siguen a su nombre.
+pub type FnCliCommand = fn(args: &[String]) -> Result<(), Error>;
+
+/// Subcomando de la línea de comandos que un módulo añade a la aplicación.
+///
+/// Los módulos habilitados declaran sus subcomandos en [`ModuleTrait::commands()`]. Se ejecutan
+/// con `<aplicación> <nombre> [argumentos...]` después de inicializar los módulos, pero sin
+/// arrancar el servidor web.
+///
+/// [`ModuleTrait::commands()`]: crate::core::module::ModuleTrait::commands
+pub struct CliCommand {
+    name: &'static str,
+    about: &'static str,
+    run: FnCliCommand,
+}
+
+impl CliCommand {
+    pub fn new(name: &'static str, run: FnCliCommand) -> Self {
+        CliCommand {
+            name,
+            about: "",
+            run,
+        }
+    }
+
+    pub fn run(&self, args: &[String]) -> Result<(), Error> {
+        (self.run)(args)
+    }
+
+    // CliCommand BUILDER.
+
+    pub fn with_about(mut self, about: &'static str) -> Self {
+        self.about = about;
+        self
+    }
+
+    // CliCommand GETTERS.
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn about(&self) -> &'static str {
+        self.about
+    }
+}
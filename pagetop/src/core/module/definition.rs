@@ -1,10 +1,13 @@
 use crate::core::action::Action;
+use crate::core::module::{CliCommand, Health, Permission, ScheduledTask};
 use crate::core::theme::ThemeRef;
 use crate::locale::L10n;
-use crate::{actions, service, util, HasHandle};
+use crate::{actions, service, util, HasHandle, Weight};
+
+use std::net::SocketAddr;
 
 #[cfg(feature = "database")]
-use crate::{db::MigrationItem, migrations};
+use crate::{db::DbErr, db::MigrationItem, migrations};
 
 pub type ModuleRef = &'static dyn ModuleTrait;
 
@@ -13,6 +16,11 @@ pub trait ModuleBase {
 }
 
 /// Los módulos deben implementar este *trait*.
+///
+/// Para sobrescribir los métodos asíncronos, como [`health_check()`](Self::health_check),
+/// [`on_server_started()`](Self::on_server_started) u [`on_shutdown()`](Self::on_shutdown), la
+/// implementación debe usar `#[async_trait::async_trait(?Send)]`.
+#[async_trait::async_trait(?Send)]
 pub trait ModuleTrait: HasHandle + ModuleBase + Send + Sync {
     fn name(&self) -> L10n {
         L10n::n(self.single_name())
@@ -26,6 +34,19 @@ pub trait ModuleTrait: HasHandle + ModuleBase + Send + Sync {
         None
     }
 
+    /// Versión del módulo (*semver*), normalmente la del *crate* que lo define asignada con
+    /// [`module_version!`](crate::module_version). Sólo se comprueba si hay que actualizar los
+    /// módulos que declaran su versión. Por defecto `None`.
+    fn version(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Versiones de PageTop con las que es compatible el módulo, como requisito *semver* (por
+    /// ejemplo, `"^0.0.50"`). Se comprueba al preparar la aplicación. Por defecto `"*"`.
+    fn requires_pagetop(&self) -> &'static str {
+        "*"
+    }
+
     fn dependencies(&self) -> Vec<ModuleRef> {
         vec![]
     }
@@ -34,20 +55,71 @@ pub trait ModuleTrait: HasHandle + ModuleBase + Send + Sync {
         vec![]
     }
 
+    /// Peso para ordenar los módulos que no dependen entre sí: los de menor peso se registran e
+    /// inicializan antes. Las dependencias siempre van antes que los módulos que las usan, sea cual
+    /// sea su peso. Ver [`enabled_modules()`](crate::core::module::enabled_modules).
+    fn weight(&self) -> Weight {
+        0
+    }
+
     fn actions(&self) -> Vec<Action> {
         actions![]
     }
 
+    /// Permisos que define el módulo. Se registran antes de inicializar los módulos.
+    fn permissions(&self) -> Vec<Permission> {
+        vec![]
+    }
+
     fn init(&self) {}
 
+    /// Subcomandos que el módulo añade a la línea de comandos de la aplicación.
+    fn commands(&self) -> Vec<CliCommand> {
+        vec![]
+    }
+
+    /// Tareas que el módulo ejecuta periódicamente. Ver [`ScheduledTask`].
+    fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+        vec![]
+    }
+
     #[cfg(feature = "database")]
     #[allow(unused_variables)]
     fn migrations(&self) -> Vec<MigrationItem> {
         migrations![]
     }
 
+    /// Se ejecuta al preparar la aplicación, después de aplicar las migraciones, si la versión del
+    /// módulo registrada en la base de datos, `from_version`, es distinta de
+    /// [`version()`](Self::version). Si devuelve un error se volverá a ejecutar en el siguiente
+    /// inicio.
+    #[cfg(feature = "database")]
+    #[allow(unused_variables)]
+    async fn update(&self, from_version: &str) -> Result<(), DbErr> {
+        Ok(())
+    }
+
+    /// Configura los servicios del módulo. Las rutas se declaran con
+    /// [`service_for_route!`](crate::service_for_route) y los archivos estáticos con
+    /// [`service_for_static_files!`](crate::service_for_static_files) para que el servidor las
+    /// conozca, por ejemplo, al listarlas con el subcomando `routes`.
     #[allow(unused_variables)]
     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {}
+
+    /// Comprueba si el módulo está listo para atender peticiones. Se consulta desde la ruta de
+    /// *readiness* (`SETTINGS.server.ready_path`); `None` indica que el módulo no tiene nada que
+    /// comprobar.
+    async fn health_check(&self) -> Option<Health> {
+        None
+    }
+
+    /// Se ejecuta cuando el servidor web ya escucha en las direcciones `addrs`.
+    #[allow(unused_variables)]
+    async fn on_server_started(&self, addrs: &[SocketAddr]) {}
+
+    /// Se ejecuta al detener ordenadamente el servidor web, en orden inverso al de habilitación de
+    /// los módulos, antes de enviar las últimas trazas y eventos pendientes.
+    async fn on_shutdown(&self) {}
 }
 
 impl<M: ?Sized + ModuleTrait> ModuleBase for M {
@@ -55,3 +127,26 @@ impl<M: ?Sized + ModuleTrait> ModuleBase for M {
         util::single_type_name::<Self>()
     }
 }
+
+/// Declara como versión del módulo la del *crate* que lo define (`CARGO_PKG_VERSION`). Se usa dentro
+/// de la implementación de [`ModuleTrait`].
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// struct Blog;
+///
+/// impl_handle!(MODULE_BLOG for Blog);
+///
+/// impl ModuleTrait for Blog {
+///     module_version!();
+/// }
+/// ```
+#[macro_export]
+macro_rules! module_version {
+    () => {
+        fn version(&self) -> Option<&'static str> {
+            Some(env!("CARGO_PKG_VERSION"))
+        }
+    };
+}
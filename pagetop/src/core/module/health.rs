@@ -0,0 +1,48 @@
+use serde::Serialize;
+
+/// Estado de una comprobación de salud.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+/// Resultado de una comprobación de salud, con su estado y un mensaje descriptivo.
+///
+/// Los módulos lo devuelven en [`ModuleTrait::health_check()`] para indicar si están listos para
+/// atender peticiones.
+///
+/// [`ModuleTrait::health_check()`]: crate::core::module::ModuleTrait::health_check
+#[derive(Clone, Debug, Serialize)]
+pub struct Health {
+    status: HealthStatus,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    message: String,
+}
+
+impl Health {
+    pub fn up(message: impl Into<String>) -> Self {
+        Health {
+            status: HealthStatus::Up,
+            message: message.into(),
+        }
+    }
+
+    pub fn down(message: impl Into<String>) -> Self {
+        Health {
+            status: HealthStatus::Down,
+            message: message.into(),
+        }
+    }
+
+    // Health GETTERS.
+
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,54 @@
+use crate::locale::L10n;
+
+/// Permiso que un módulo define para controlar el acceso a sus funcionalidades.
+///
+/// Los módulos habilitados declaran sus permisos en [`ModuleTrait::permissions()`] con un nombre
+/// interno único (por ejemplo, `"administer users"`), un título y una descripción traducibles. Los
+/// permisos se conceden a los roles con [`grant_permission()`] y se comprueban en las peticiones
+/// con [`check_permission()`].
+///
+/// [`ModuleTrait::permissions()`]: crate::core::module::ModuleTrait::permissions
+/// [`grant_permission()`]: crate::service::grant_permission
+/// [`check_permission()`]: crate::service::check_permission
+#[derive(Clone)]
+pub struct Permission {
+    name: &'static str,
+    title: L10n,
+    description: L10n,
+}
+
+impl Permission {
+    pub fn new(name: &'static str) -> Self {
+        Permission {
+            name,
+            title: L10n::n(name),
+            description: L10n::default(),
+        }
+    }
+
+    // Permission BUILDER.
+
+    pub fn with_title(mut self, title: L10n) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub fn with_description(mut self, description: L10n) -> Self {
+        self.description = description;
+        self
+    }
+
+    // Permission GETTERS.
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn title(&self) -> &L10n {
+        &self.title
+    }
+
+    pub fn description(&self) -> &L10n {
+        &self.description
+    }
+}
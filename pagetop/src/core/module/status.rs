@@ -0,0 +1,153 @@
+use crate::db::*;
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Estado de un módulo guardado en la tabla `module_status` de la base de datos.
+///
+/// Los módulos sin estado guardado se instalan automáticamente al preparar la aplicación.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ModuleStatus {
+    /// Módulo instalado y activo.
+    Installed,
+    /// Módulo instalado pero inactivo. No se configuran sus rutas ni se registran sus acciones, ni
+    /// se inicializa, pero conserva sus datos.
+    Disabled,
+    /// Módulo inactivo con sus migraciones deshechas.
+    Uninstalled,
+}
+
+impl ModuleStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ModuleStatus::Installed => "installed",
+            ModuleStatus::Disabled => "disabled",
+            ModuleStatus::Uninstalled => "uninstalled",
+        }
+    }
+
+    fn parse(status: &str) -> Option<Self> {
+        match status {
+            "installed" => Some(ModuleStatus::Installed),
+            "disabled" => Some(ModuleStatus::Disabled),
+            "uninstalled" => Some(ModuleStatus::Uninstalled),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ModuleStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[rustfmt::skip]
+#[derive(Iden)]
+enum ModuleStatusTable {
+    #[iden = "module_status"]
+    Table,              // module_status: Runtime status of the compiled-in modules.
+
+    Name,               // Primary Key: Module name.
+    Status,             // Module status: "installed", "disabled" or "uninstalled".
+    Version,            // Module version when it was installed or last updated.
+}
+
+// Estado y versión registrados de un módulo.
+#[derive(Clone)]
+pub(crate) struct ModuleRecord {
+    pub status: ModuleStatus,
+    pub version: String,
+}
+
+// Crea la tabla si no existe y devuelve el estado y la versión registrados de cada módulo.
+pub(crate) async fn load() -> Result<HashMap<String, ModuleRecord>, DbErr> {
+    let dbconn = match DBCONN.get() {
+        Some(dbconn) => dbconn,
+        None => {
+            return Err(DbErr::Conn(RuntimeErr::Internal(
+                DBCONN_NOT_INITIALIZED.to_owned(),
+            )))
+        }
+    };
+    SchemaManager::new(SchemaManagerConnection::Connection(dbconn))
+        .create_table(
+            Table::create()
+                .table(ModuleStatusTable::Table)
+                .if_not_exists()
+                .col(
+                    ColumnDef::new(ModuleStatusTable::Name)
+                        .string_len(255)
+                        .not_null()
+                        .primary_key(),
+                )
+                .col(
+                    ColumnDef::new(ModuleStatusTable::Status)
+                        .string_len(16)
+                        .not_null(),
+                )
+                .col(
+                    ColumnDef::new(ModuleStatusTable::Version)
+                        .string_len(64)
+                        .not_null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+    let mut records = HashMap::new();
+    for row in query::<SelectStatement>(
+        Query::select()
+            .columns([
+                ModuleStatusTable::Name,
+                ModuleStatusTable::Status,
+                ModuleStatusTable::Version,
+            ])
+            .from(ModuleStatusTable::Table),
+    )
+    .await?
+    .iter()
+    {
+        let name: String = row.try_get("", "name")?;
+        let value: String = row.try_get("", "status")?;
+        let version: String = row.try_get("", "version")?;
+        match ModuleStatus::parse(&value) {
+            Some(status) => {
+                records.insert(name, ModuleRecord { status, version });
+            }
+            None => {
+                return Err(DbErr::Custom(format!(
+                    "Invalid status \"{}\" for module \"{}\"",
+                    value, name
+                )))
+            }
+        }
+    }
+    Ok(records)
+}
+
+// Guarda el estado y la versión del módulo.
+pub(crate) async fn save(name: &str, record: &ModuleRecord) -> Result<(), DbErr> {
+    exec::<DeleteStatement>(
+        Query::delete()
+            .from_table(ModuleStatusTable::Table)
+            .and_where(Expr::col(ModuleStatusTable::Name).eq(name)),
+    )
+    .await?;
+    exec::<InsertStatement>(
+        Query::insert()
+            .into_table(ModuleStatusTable::Table)
+            .columns([
+                ModuleStatusTable::Name,
+                ModuleStatusTable::Status,
+                ModuleStatusTable::Version,
+            ])
+            .values_panic([
+                name.into(),
+                record.status.as_str().into(),
+                record.version.as_str().into(),
+            ]),
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use crate::datetime::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+use std::fmt;
+use std::time::Duration;
+
+/// Función que ejecuta una tarea programada. Si falla devuelve la descripción del error.
+pub type FnScheduledTask = fn() -> Result<(), String>;
+
+/// Programación de una tarea.
+#[derive(Clone, Copy)]
+pub enum Schedule {
+    /// Cada cierto tiempo, contado desde que arranca el servidor web.
+    Every(Duration),
+    /// Expresión *cron* de cinco campos (minuto, hora, día del mes, mes y día de la semana) en hora
+    /// UTC, por ejemplo `"*/15 * * * *"` o `"30 3 * * 1-5"`. Admite listas (`1,15`), rangos
+    /// (`1-5`), incrementos (`*/10`) y las abreviaturas `@hourly`, `@daily`, `@weekly`, `@monthly`
+    /// y `@yearly`.
+    Cron(&'static str),
+}
+
+impl Schedule {
+    /// Comprueba que la programación es válida.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Schedule::Every(interval) if interval.is_zero() => {
+                Err("Interval must be greater than zero".to_owned())
+            }
+            Schedule::Every(_) => Ok(()),
+            Schedule::Cron(expr) => CronExpr::parse(expr).map(|_| ()),
+        }
+    }
+
+    /// Tiempo que falta para la siguiente ejecución, o `None` si la programación no es válida.
+    pub(crate) fn next_delay(&self) -> Option<Duration> {
+        match self {
+            Schedule::Every(interval) if interval.is_zero() => None,
+            Schedule::Every(interval) => Some(*interval),
+            Schedule::Cron(expr) => {
+                let now = Utc::now().naive_utc();
+                let next = CronExpr::parse(expr).ok()?.next_after(now)?;
+                (next - now).to_std().ok()
+            }
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Every(interval) => write!(f, "every {}s", interval.as_secs()),
+            Schedule::Cron(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+/// Tarea que un módulo ejecuta periódicamente, como eliminar datos caducados o publicar contenidos
+/// programados.
+///
+/// Los módulos habilitados declaran sus tareas en [`ModuleTrait::scheduled_tasks()`]. El servidor
+/// web las ejecuta según su programación (salvo que se desactive con `scheduled_tasks` en la sección
+/// `[server]` de la configuración), y el subcomando `cron run` las ejecuta una vez para usarlas
+/// desde un programador externo. Cada tarea se ejecuta en un hilo aparte, sin solaparse consigo
+/// misma.
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// struct Blog;
+///
+/// impl_handle!(MODULE_BLOG for Blog);
+///
+/// impl ModuleTrait for Blog {
+///     fn scheduled_tasks(&self) -> Vec<ScheduledTask> {
+///         vec![ScheduledTask::new("publish", Schedule::Cron("*/5 * * * *"), publish)]
+///     }
+/// }
+///
+/// fn publish() -> Result<(), String> {
+///     Ok(())
+/// }
+/// ```
+///
+/// [`ModuleTrait::scheduled_tasks()`]: crate::core::module::ModuleTrait::scheduled_tasks
+#[derive(Clone, Copy)]
+pub struct ScheduledTask {
+    name: &'static str,
+    schedule: Schedule,
+    run: FnScheduledTask,
+}
+
+impl ScheduledTask {
+    pub fn new(name: &'static str, schedule: Schedule, run: FnScheduledTask) -> Self {
+        ScheduledTask {
+            name,
+            schedule,
+            run,
+        }
+    }
+
+    pub fn run(&self) -> Result<(), String> {
+        (self.run)()
+    }
+
+    // ScheduledTask GETTERS.
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn schedule(&self) -> Schedule {
+        self.schedule
+    }
+}
+
+/// Resultado de la última ejecución de una tarea programada.
+#[derive(Clone)]
+pub struct TaskRun {
+    started: DateTime<Utc>,
+    duration: Duration,
+    outcome: Result<(), String>,
+}
+
+impl TaskRun {
+    pub(crate) fn new(
+        started: DateTime<Utc>,
+        duration: Duration,
+        outcome: Result<(), String>,
+    ) -> Self {
+        TaskRun {
+            started,
+            duration,
+            outcome,
+        }
+    }
+
+    // TaskRun GETTERS.
+
+    pub fn started(&self) -> DateTime<Utc> {
+        self.started
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn outcome(&self) -> &Result<(), String> {
+        &self.outcome
+    }
+}
+
+// CRON EXPRESSIONS ********************************************************************************
+
+// Valores permitidos de cada campo como máscara de bits.
+struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Si el día del mes o de la semana no se restringen ("*"), basta con que coincida el otro.
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl CronExpr {
+    fn parse(expr: &str) -> Result<CronExpr, String> {
+        let expr = match expr.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            expr => expr,
+        };
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!(
+                "Cron expression \"{}\" must have five fields",
+                expr
+            ));
+        }
+        let weekdays = parse_field(fields[4], 0, 7)?;
+        Ok(CronExpr {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            // El 7 también es domingo.
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*'),
+        })
+    }
+
+    // Primer minuto posterior a `after` que cumple la expresión, en los próximos cinco años.
+    fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut t = after.with_second(0)?.with_nanosecond(0)? + chrono::Duration::minutes(1);
+        let limit = t + chrono::Duration::days(5 * 366);
+        while t < limit {
+            if !has(self.months, t.month()) {
+                let (year, month) = match t.month() {
+                    12 => (t.year() + 1, 1),
+                    month => (t.year(), month + 1),
+                };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+            } else if !self.day_matches(&t) {
+                t = (t.date() + chrono::Duration::days(1)).and_hms_opt(0, 0, 0)?;
+            } else if !has(self.hours, t.hour()) {
+                t = t.date().and_hms_opt(t.hour(), 0, 0)? + chrono::Duration::hours(1);
+            } else if !has(self.minutes, t.minute()) {
+                t += chrono::Duration::minutes(1);
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, t: &NaiveDateTime) -> bool {
+        let day = has(self.days, t.day());
+        let weekday = has(self.weekdays, t.weekday().num_days_from_sunday());
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+}
+
+fn has(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+// Campo con listas de valores, rangos e incrementos, por ejemplo "1-5", "*/10" o "0,30".
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let invalid = || format!("Invalid cron field \"{}\"", field);
+    let mut mask = 0;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        let (first, last) = match range {
+            "*" => (min, max),
+            range => match range.split_once('-') {
+                Some((first, last)) => (
+                    first.parse::<u32>().map_err(|_| invalid())?,
+                    last.parse::<u32>().map_err(|_| invalid())?,
+                ),
+                None => {
+                    let first = range.parse::<u32>().map_err(|_| invalid())?;
+                    (first, if part.contains('/') { max } else { first })
+                }
+            },
+        };
+        if step == 0 || first < min || last > max || first > last {
+            return Err(invalid());
+        }
+        for value in (first..=last).step_by(step as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(datetime: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn next(expr: &str, after: &str) -> NaiveDateTime {
+        CronExpr::parse(expr)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_field_values() {
+        assert_eq!(parse_field("5", 0, 59).unwrap(), 1 << 5);
+        assert_eq!(parse_field("0,30", 0, 59).unwrap(), 1 | 1 << 30);
+        assert_eq!(parse_field("1-3", 1, 12).unwrap(), 1 << 1 | 1 << 2 | 1 << 3);
+        assert_eq!(
+            parse_field("9-17/4", 0, 23).unwrap(),
+            1 << 9 | 1 << 13 | 1 << 17
+        );
+        assert_eq!(parse_field("*/20", 0, 59).unwrap(), 1 | 1 << 20 | 1 << 40);
+        assert_eq!(parse_field("50/5", 0, 59).unwrap(), 1 << 50 | 1 << 55);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(CronExpr::parse("* * * *").is_err());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("* * 0 * *").is_err());
+        assert!(CronExpr::parse("5-1 * * * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+        assert!(CronExpr::parse("@often").is_err());
+    }
+
+    #[test]
+    fn test_next_minute() {
+        assert_eq!(
+            next("* * * * *", "2023-01-01 10:00"),
+            at("2023-01-01 10:01")
+        );
+        assert_eq!(
+            next("*/15 * * * *", "2023-01-01 10:01"),
+            at("2023-01-01 10:15")
+        );
+        assert_eq!(
+            next("0,30 * * * *", "2023-01-01 10:30"),
+            at("2023-01-01 11:00")
+        );
+    }
+
+    #[test]
+    fn test_ranges_and_steps() {
+        assert_eq!(
+            next("0 9-17/4 * * *", "2023-01-01 09:00"),
+            at("2023-01-01 13:00")
+        );
+        assert_eq!(
+            next("0 9-17/4 * * *", "2023-01-01 17:00"),
+            at("2023-01-02 09:00")
+        );
+        // De lunes a viernes, el 2023-01-06 es viernes.
+        assert_eq!(
+            next("30 3 * * 1-5", "2023-01-06 04:00"),
+            at("2023-01-09 03:30")
+        );
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        assert_eq!(next("@hourly", "2023-01-01 10:20"), at("2023-01-01 11:00"));
+        assert_eq!(next("@daily", "2023-01-01 10:20"), at("2023-01-02 00:00"));
+        assert_eq!(next("@weekly", "2023-01-02 00:00"), at("2023-01-08 00:00"));
+        assert_eq!(next("@monthly", "2023-01-02 00:00"), at("2023-02-01 00:00"));
+    }
+
+    #[test]
+    fn test_sunday_as_seven() {
+        // El 2023-01-07 es sábado.
+        assert_eq!(
+            next("0 0 * * 7", "2023-01-07 12:00"),
+            at("2023-01-08 00:00")
+        );
+        assert_eq!(
+            next("0 0 * * 0", "2023-01-07 12:00"),
+            at("2023-01-08 00:00")
+        );
+        assert_eq!(
+            next("0 0 * * 5-7", "2023-01-08 12:00"),
+            at("2023-01-13 00:00")
+        );
+    }
+
+    #[test]
+    fn test_day_of_month_or_weekday() {
+        // El día 10 o cualquier viernes.
+        assert_eq!(
+            next("0 0 10 * 5", "2023-01-01 00:00"),
+            at("2023-01-06 00:00")
+        );
+        assert_eq!(
+            next("0 0 10 * 5", "2023-01-06 00:00"),
+            at("2023-01-10 00:00")
+        );
+        assert_eq!(
+            next("0 0 10 * 5", "2023-01-10 00:00"),
+            at("2023-01-13 00:00")
+        );
+        // Si el día de la semana no se restringe, sólo cuenta el día del mes, y al revés.
+        assert_eq!(
+            next("0 0 10 * *", "2023-01-01 00:00"),
+            at("2023-01-10 00:00")
+        );
+        assert_eq!(
+            next("0 0 * * 5", "2023-01-07 00:00"),
+            at("2023-01-13 00:00")
+        );
+    }
+
+    #[test]
+    fn test_month_and_year_rollover() {
+        assert_eq!(
+            next("0 0 1 * *", "2023-01-31 12:00"),
+            at("2023-02-01 00:00")
+        );
+        assert_eq!(
+            next("0 0 31 * *", "2023-01-31 12:00"),
+            at("2023-03-31 00:00")
+        );
+        assert_eq!(next("@yearly", "2023-12-31 23:59"), at("2024-01-01 00:00"));
+        assert_eq!(
+            next("30 3 * 2 *", "2023-03-01 00:00"),
+            at("2024-02-01 03:30")
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2023-01-01 00:00"),
+            at("2024-02-29 00:00")
+        );
+    }
+}
@@ -2,7 +2,8 @@ mod definition;
 pub use definition::{ThemeRef, ThemeTrait};
 
 mod regions;
-pub(crate) use regions::ComponentsRegions;
-pub use regions::{add_component_in, Region};
+pub use regions::{add_component_in, registered_regions, Region};
+pub(crate) use regions::{component_handles, ComponentsRegions};
 
 pub(crate) mod all;
+pub use all::themes;
@@ -8,6 +8,11 @@ use std::sync::RwLock;
 
 pub static THEMES: LazyStatic<RwLock<Vec<ThemeRef>>> = LazyStatic::new(|| RwLock::new(Vec::new()));
 
+/// Devuelve los temas disponibles, los de los módulos habilitados.
+pub fn themes() -> Vec<ThemeRef> {
+    THEMES.read().unwrap().clone()
+}
+
 // DEFAULT THEME ***********************************************************************************
 
 pub static THEME: LazyStatic<ThemeRef> =
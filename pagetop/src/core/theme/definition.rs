@@ -1,4 +1,5 @@
-use crate::core::component::{ComponentTrait, Context};
+use crate::base::action;
+use crate::core::component::{ComponentTrait, Context, RenderCache};
 use crate::core::module::ModuleTrait;
 use crate::html::{html, Favicon, Markup, OptionId};
 use crate::locale::L10n;
@@ -20,8 +21,36 @@ pub trait ThemeTrait: ModuleTrait + Send + Sync {
         ]
     }
 
+    /// Configuración de la caché del código renderizado de la región `region`. Por defecto no se
+    /// guarda en caché.
+    #[allow(unused_variables)]
+    fn region_cache(&self, region: &str) -> Option<RenderCache> {
+        None
+    }
+
     fn prepare_region(&self, page: &mut Page, region: &str) -> Markup {
-        let render_region = page.components_in(region).render(page.context());
+        let render_components = |page: &mut Page| {
+            let mut components = page.components_in(region);
+
+            // Module actions to alter the components of the region before rendering it.
+            action::page::AlterRegionComponents::dispatch(&mut components, page.context(), region);
+
+            components.render(page.context())
+        };
+        let render_region = match self.region_cache(region) {
+            Some(cache) => {
+                let base = concat_string!("region:", self.single_name(), ":", region);
+                match cache.lookup(&base, page.context()) {
+                    Ok(markup) => markup,
+                    Err(key) => {
+                        let markup = render_components(page);
+                        cache.store(key, page.context(), &markup);
+                        markup
+                    }
+                }
+            }
+            None => render_components(page),
+        };
         if render_region.is_empty() {
             html! {}
         } else {
@@ -43,6 +43,53 @@ impl ComponentsRegions {
     }
 }
 
+impl ComponentsRegions {
+    // Regiones con los nombres de los tipos de sus componentes, ordenadas por nombre de región.
+    fn summary(&self) -> Vec<(&'static str, Vec<String>)> {
+        let mut regions: Vec<_> = self
+            .0
+            .iter()
+            .map(|(region, components)| (*region, components.type_names()))
+            .collect();
+        regions.sort_by_key(|(region, _)| *region);
+        regions
+    }
+}
+
+/// Devuelve los componentes añadidos con [`add_component_in()`] a cada región: el identificador
+/// del tema para las regiones de un tema ([`Region::OfTheme`]), o `None` para las regiones comunes
+/// a todos los temas ([`Region::Named`]), el nombre de la región y los nombres de los tipos de sus
+/// componentes.
+pub fn registered_regions() -> Vec<(Option<Handle>, &'static str, Vec<String>)> {
+    let mut regions: Vec<_> = COMMON_REGIONS
+        .read()
+        .unwrap()
+        .summary()
+        .into_iter()
+        .map(|(region, components)| (None, region, components))
+        .collect();
+    for (theme, theme_regions) in THEME_REGIONS.read().unwrap().iter() {
+        for (region, components) in theme_regions.summary().into_iter() {
+            regions.push((Some(*theme), region, components));
+        }
+    }
+    regions
+}
+
+// Identificadores de los componentes añadidos a las regiones, con sus tipos.
+pub(crate) fn component_handles() -> Vec<(Handle, &'static str)> {
+    let mut handles = Vec::new();
+    for components in COMMON_REGIONS.read().unwrap().0.values() {
+        handles.extend(components.type_handles());
+    }
+    for regions in THEME_REGIONS.read().unwrap().values() {
+        for components in regions.0.values() {
+            handles.extend(components.type_handles());
+        }
+    }
+    handles
+}
+
 pub enum Region {
     Named(&'static str),
     OfTheme(ThemeRef, &'static str),
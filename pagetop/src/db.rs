@@ -1,85 +1,93 @@
 //! Acceso unificado y normalizado a base de datos.
 
-use crate::{config, trace, LazyStatic};
+use crate::{config, trace};
 
 pub use url::Url as DbUri;
 
 pub use sea_orm::error::{DbErr, RuntimeErr};
 pub use sea_orm::{DatabaseConnection as DbConn, ExecResult, QueryResult};
 
+use once_cell::sync::OnceCell;
+
 use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseBackend, Statement};
 
 pub(crate) use futures::executor::block_on as run_now;
 
-const DBCONN_NOT_INITIALIZED: &str = "Database connection not initialized";
-
-pub(crate) static DBCONN: LazyStatic<Option<DbConn>> = LazyStatic::new(|| {
-    if !config::SETTINGS.database.db_name.trim().is_empty() {
-        trace::info!(
-            "Connecting to database \"{}\" using a pool of {} connections",
-            &config::SETTINGS.database.db_name,
-            &config::SETTINGS.database.max_pool_size
-        );
-
-        let db_uri = match config::SETTINGS.database.db_type.as_str() {
-            "mysql" | "postgres" => {
-                let mut tmp_uri = DbUri::parse(
-                    format!(
-                        "{}://{}/{}",
-                        &config::SETTINGS.database.db_type,
-                        &config::SETTINGS.database.db_host,
-                        &config::SETTINGS.database.db_name
-                    )
-                    .as_str(),
-                )
-                .unwrap();
-                tmp_uri
-                    .set_username(config::SETTINGS.database.db_user.as_str())
-                    .unwrap();
-                // https://github.com/launchbadge/sqlx/issues/1624
-                tmp_uri
-                    .set_password(Some(config::SETTINGS.database.db_pass.as_str()))
-                    .unwrap();
-                if config::SETTINGS.database.db_port != 0 {
-                    tmp_uri
-                        .set_port(Some(config::SETTINGS.database.db_port))
-                        .unwrap();
-                }
-                tmp_uri
-            }
-            "sqlite" => DbUri::parse(
+pub(crate) const DBCONN_NOT_INITIALIZED: &str = "Database connection not initialized";
+
+pub(crate) static DBCONN: OnceCell<DbConn> = OnceCell::new();
+
+/// Conecta con la base de datos según los ajustes de [`[database]`](config::Database). Si no se
+/// indica ninguna base de datos no hace nada.
+pub(crate) fn connect() -> Result<(), DbErr> {
+    if DBCONN.get().is_some() || config::SETTINGS.database.db_name.trim().is_empty() {
+        return Ok(());
+    }
+
+    trace::info!(
+        "Connecting to database \"{}\" using a pool of {} connections",
+        &config::SETTINGS.database.db_name,
+        &config::SETTINGS.database.max_pool_size
+    );
+
+    let db_uri = match config::SETTINGS.database.db_type.as_str() {
+        "mysql" | "postgres" => {
+            let mut tmp_uri = DbUri::parse(
                 format!(
-                    "{}://{}",
+                    "{}://{}/{}",
                     &config::SETTINGS.database.db_type,
+                    &config::SETTINGS.database.db_host,
                     &config::SETTINGS.database.db_name
                 )
                 .as_str(),
             )
-            .unwrap(),
-            _ => {
-                trace::error!(
-                    "Unrecognized database type \"{}\"",
-                    &config::SETTINGS.database.db_type
-                );
-                DbUri::parse("").unwrap()
+            .map_err(invalid_uri)?;
+            tmp_uri
+                .set_username(config::SETTINGS.database.db_user.as_str())
+                .map_err(|_| invalid_uri("db_user"))?;
+            // https://github.com/launchbadge/sqlx/issues/1624
+            tmp_uri
+                .set_password(Some(config::SETTINGS.database.db_pass.as_str()))
+                .map_err(|_| invalid_uri("db_pass"))?;
+            if config::SETTINGS.database.db_port != 0 {
+                tmp_uri
+                    .set_port(Some(config::SETTINGS.database.db_port))
+                    .map_err(|_| invalid_uri("db_port"))?;
             }
-        };
-
-        Some(
-            run_now(Database::connect::<ConnectOptions>({
-                let mut db_opt = ConnectOptions::new(db_uri.to_string());
-                db_opt.max_connections(config::SETTINGS.database.max_pool_size);
-                db_opt
-            }))
-            .unwrap_or_else(|_| panic!("Failed to connect to database")),
+            tmp_uri
+        }
+        "sqlite" => DbUri::parse(
+            format!(
+                "{}://{}",
+                &config::SETTINGS.database.db_type,
+                &config::SETTINGS.database.db_name
+            )
+            .as_str(),
         )
-    } else {
-        None
-    }
-});
+        .map_err(invalid_uri)?,
+        _ => {
+            return Err(DbErr::Custom(format!(
+                "Unrecognized database type \"{}\"",
+                &config::SETTINGS.database.db_type
+            )))
+        }
+    };
+
+    let dbconn = run_now(Database::connect::<ConnectOptions>({
+        let mut db_opt = ConnectOptions::new(db_uri.to_string());
+        db_opt.max_connections(config::SETTINGS.database.max_pool_size);
+        db_opt
+    }))?;
+    let _ = DBCONN.set(dbconn);
+    Ok(())
+}
+
+fn invalid_uri(e: impl std::fmt::Display) -> DbErr {
+    DbErr::Custom(format!("Invalid database settings ({})", e))
+}
 
 pub async fn query<Q: QueryStatementWriter>(stmt: &mut Q) -> Result<Vec<QueryResult>, DbErr> {
-    match &*DBCONN {
+    match DBCONN.get() {
         Some(dbconn) => {
             let dbbackend = dbconn.get_database_backend();
             dbconn
@@ -100,7 +108,7 @@ pub async fn query<Q: QueryStatementWriter>(stmt: &mut Q) -> Result<Vec<QueryRes
 }
 
 pub async fn exec<Q: QueryStatementWriter>(stmt: &mut Q) -> Result<Option<QueryResult>, DbErr> {
-    match &*DBCONN {
+    match DBCONN.get() {
         Some(dbconn) => {
             let dbbackend = dbconn.get_database_backend();
             dbconn
@@ -121,7 +129,7 @@ pub async fn exec<Q: QueryStatementWriter>(stmt: &mut Q) -> Result<Option<QueryR
 }
 
 pub async fn exec_raw(stmt: String) -> Result<ExecResult, DbErr> {
-    match &*DBCONN {
+    match DBCONN.get() {
         Some(dbconn) => {
             let dbbackend = dbconn.get_database_backend();
             dbconn
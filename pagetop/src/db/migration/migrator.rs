@@ -31,6 +31,18 @@ pub struct Migration {
     status: MigrationStatus,
 }
 
+impl Migration {
+    /// Get migration name from MigrationName trait implementation
+    pub fn name(&self) -> &str {
+        self.migration.name()
+    }
+
+    /// Get migration status
+    pub fn status(&self) -> &MigrationStatus {
+        &self.status
+    }
+}
+
 impl Display for MigrationStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let status = match self {
@@ -9,6 +9,7 @@ pub use assets::headstyles::HeadStyles;
 pub use assets::javascript::{JavaScript, ModeJS};
 pub use assets::stylesheet::{StyleSheet, TargetMedia};
 pub use assets::Assets;
+pub(crate) use assets::AssetsTrait;
 
 mod favicon;
 pub use favicon::Favicon;
@@ -42,6 +42,10 @@ impl<T: AssetsTrait> Assets<T> {
         self
     }
 
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
     pub fn prepare(&mut self) -> Markup {
         let assets = &mut self.0;
         assets.sort_by_key(|a| a.weight());
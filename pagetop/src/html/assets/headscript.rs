@@ -3,11 +3,12 @@ use crate::html::{html, Markup};
 use crate::Weight;
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct HeadScript {
     path  : String,
     code  : String,
     weight: Weight,
+    nonce : Option<String>,
 }
 
 impl AssetsTrait for HeadScript {
@@ -20,7 +21,7 @@ impl AssetsTrait for HeadScript {
     }
 
     fn prepare(&self) -> Markup {
-        html! { script { (self.code) }; }
+        html! { script nonce=[&self.nonce] { (self.code) }; }
     }
 }
 
@@ -41,4 +42,9 @@ impl HeadScript {
         self.weight = value;
         self
     }
+
+    pub(crate) fn with_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
 }
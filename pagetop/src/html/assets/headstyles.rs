@@ -3,11 +3,12 @@ use crate::html::{html, Markup};
 use crate::Weight;
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct HeadStyles {
     path  : String,
     styles: String,
     weight: Weight,
+    nonce : Option<String>,
 }
 
 impl AssetsTrait for HeadStyles {
@@ -20,7 +21,7 @@ impl AssetsTrait for HeadStyles {
     }
 
     fn prepare(&self) -> Markup {
-        html! { styles { (self.styles) }; }
+        html! { style nonce=[&self.nonce] { (self.styles) }; }
     }
 }
 
@@ -41,4 +42,9 @@ impl HeadStyles {
         self.weight = value;
         self
     }
+
+    pub(crate) fn with_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
 }
@@ -2,7 +2,7 @@ use crate::html::assets::AssetsTrait;
 use crate::html::{html, Markup};
 use crate::Weight;
 
-#[derive(Default, Eq, PartialEq)]
+#[derive(Clone, Default, Eq, PartialEq)]
 pub enum ModeJS {
     Async,
     #[default]
@@ -11,7 +11,7 @@ pub enum ModeJS {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct JavaScript {
     path   : String,
     prefix : &'static str,
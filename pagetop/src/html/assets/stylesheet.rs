@@ -10,7 +10,7 @@ pub enum TargetMedia {
 }
 
 #[rustfmt::skip]
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct StyleSheet {
     path   : String,
     prefix : &'static str,
@@ -54,7 +54,7 @@
 //!     }
 //!
 //!     fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
-//!         scfg.route("/", service::web::get().to(hello_world));
+//!         service_for_route!(scfg, get "/" => hello_world);
 //!     }
 //! }
 //!
@@ -106,10 +106,13 @@
 
 pub use concat_string::concat_string;
 
+/// Define métodos asíncronos en *traits*, como los de [`ModuleTrait`](core::module::ModuleTrait).
+pub use async_trait;
+
 /// Enables flexible identifier concatenation in macros, allowing new items with pasted identifiers.
 pub use paste::paste;
 
-pub use pagetop_macros::{fn_builder, main, test};
+pub use pagetop_macros::{component_trait, fn_builder, main, test, Component};
 
 // *************************************************************************************************
 // GLOBAL.
@@ -126,6 +129,11 @@ pub trait HasHandle {
         Self: Sized;
 
     fn handle(&self) -> Handle;
+
+    /// Nombre completo del tipo del elemento. Ver también [`util::handle_name()`].
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
 }
 
 pub type Weight = i8;
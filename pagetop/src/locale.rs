@@ -169,7 +169,7 @@ macro_rules! static_locales {
     };
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 enum L10nOp {
     #[default]
     None,
@@ -177,7 +177,7 @@ enum L10nOp {
     Translate(String),
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct L10n {
     op: L10nOp,
     locales: Option<&'static Locales>,
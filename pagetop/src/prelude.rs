@@ -1,7 +1,8 @@
 //! The PageTop Prelude.
 
 // Re-exported macros.
-pub use crate::{concat_string, fn_builder, main, paste, test};
+pub use crate::{async_trait, concat_string, fn_builder, main, paste, test};
+pub use crate::{component_trait, Component};
 
 // Global.
 pub use crate::{Handle, HasHandle, HashMapResources, LazyStatic, Weight};
@@ -17,9 +18,11 @@ pub use crate::default_settings;
 // crate::locale
 pub use crate::static_locales;
 // crate::service
-pub use crate::{service_for_static_files, static_files};
+pub use crate::{service_for_route, service_for_static_files, static_files};
 // crate::core::actions
 pub use crate::actions;
+// crate::core::module
+pub use crate::module_version;
 
 // API.
 
@@ -51,4 +54,4 @@ pub use crate::base::action;
 pub use crate::base::component::*;
 pub use crate::base::theme;
 
-pub use crate::app::Application;
+pub use crate::app::{Application, PrepareError};
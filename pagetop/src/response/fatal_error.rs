@@ -2,10 +2,19 @@ mod error403;
 pub use error403::ERROR_403;
 mod error404;
 pub use error404::ERROR_404;
+mod error429;
+pub use error429::ERROR_429;
+mod error500;
+pub use error500::ERROR_500;
+mod error503;
+pub use error503::ERROR_503;
 
+use crate::config;
+use crate::core::component::Context;
+use crate::html::{html, Markup};
 use crate::locale::L10n;
 use crate::response::{page::Page, ResponseError};
-use crate::service::http::{header::ContentType, StatusCode};
+use crate::service::http::{header, header::ContentType, StatusCode};
 use crate::service::{HttpRequest, HttpResponse};
 
 use std::fmt;
@@ -17,7 +26,9 @@ pub enum FatalError {
     AccessDenied(HttpRequest),
     NotFound(HttpRequest),
     PreconditionFailed(HttpRequest),
+    TooManyRequests(HttpRequest),
     InternalError(HttpRequest),
+    ServiceUnavailable(HttpRequest),
     Timeout(HttpRequest),
 }
 
@@ -58,19 +69,76 @@ impl fmt::Display for FatalError {
             }
             // Error 412.
             FatalError::PreconditionFailed(_) => write!(f, "Precondition Failed"),
+            // Error 429.
+            FatalError::TooManyRequests(request) => {
+                let error_page = Page::new(request.clone());
+                if let Ok(page) = error_page
+                    .with_title(L10n::n("Error TOO MANY REQUESTS"))
+                    .with_in("content", error429::Error429)
+                    .with_template("error")
+                    .render()
+                {
+                    write!(f, "{}", page.into_string())
+                } else {
+                    write!(f, "Too Many Requests")
+                }
+            }
             // Error 500.
-            FatalError::InternalError(_) => write!(f, "Internal Error"),
+            FatalError::InternalError(request) => {
+                let error_page = Page::new(request.clone());
+                if let Ok(page) = error_page
+                    .with_title(L10n::n("Error INTERNAL SERVER ERROR"))
+                    .with_in("content", error500::Error500)
+                    .with_template("error")
+                    .render()
+                {
+                    write!(f, "{}", page.into_string())
+                } else {
+                    write!(f, "Internal Error")
+                }
+            }
+            // Error 503.
+            FatalError::ServiceUnavailable(request) => {
+                let error_page = Page::new(request.clone());
+                if let Ok(page) = error_page
+                    .with_title(L10n::n("Error SERVICE UNAVAILABLE"))
+                    .with_in("content", error503::Error503)
+                    .with_template("error")
+                    .render()
+                {
+                    write!(f, "{}", page.into_string())
+                } else {
+                    write!(f, "Service Unavailable")
+                }
+            }
             // Error 504.
             FatalError::Timeout(_) => write!(f, "Timeout"),
         }
     }
 }
 
+// Identificador de la petición para que el usuario pueda indicarlo al informar del error.
+fn request_id(cx: &Context) -> Markup {
+    html! {
+        @if let Some(request_id) = cx.request_id() {
+            p class="request-id" { ("Request ID: ") code { (request_id) } }
+        }
+    }
+}
+
 impl ResponseError for FatalError {
     fn error_response(&self) -> HttpResponse {
-        HttpResponse::build(self.status_code())
-            .insert_header(ContentType::html())
-            .body(self.to_string())
+        let mut response = HttpResponse::build(self.status_code());
+        response.insert_header(ContentType::html());
+        if let FatalError::ServiceUnavailable(_) = self {
+            if config::SETTINGS.server.maintenance_retry_after > 0 {
+                response.insert_header((
+                    header::RETRY_AFTER,
+                    config::SETTINGS.server.maintenance_retry_after,
+                ));
+            }
+        }
+        response.body(self.to_string())
     }
 
     #[rustfmt::skip]
@@ -81,7 +149,9 @@ impl ResponseError for FatalError {
             FatalError::AccessDenied(_)       => StatusCode::FORBIDDEN,
             FatalError::NotFound(_)           => StatusCode::NOT_FOUND,
             FatalError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            FatalError::TooManyRequests(_)    => StatusCode::TOO_MANY_REQUESTS,
             FatalError::InternalError(_)      => StatusCode::INTERNAL_SERVER_ERROR,
+            FatalError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             FatalError::Timeout(_)            => StatusCode::GATEWAY_TIMEOUT,
         }
     }
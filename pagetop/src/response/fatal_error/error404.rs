@@ -1,6 +1,7 @@
 use crate::core::component::{ComponentTrait, Context};
 use crate::html::{html, PrepareMarkup};
 use crate::impl_handle;
+use crate::response::fatal_error::request_id;
 
 pub struct Error404;
 
@@ -11,10 +12,11 @@ impl ComponentTrait for Error404 {
         Self
     }
 
-    fn prepare_component(&self, _cx: &mut Context) -> PrepareMarkup {
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
         PrepareMarkup::With(html! {
             div {
                 h1 { ("RESOURCE NOT FOUND") }
+                (request_id(cx))
             }
         })
     }
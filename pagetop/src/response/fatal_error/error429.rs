@@ -0,0 +1,24 @@
+use crate::core::component::{ComponentTrait, Context};
+use crate::html::{html, PrepareMarkup};
+use crate::impl_handle;
+use crate::response::fatal_error::request_id;
+
+pub struct Error429;
+
+impl_handle!(ERROR_429 for Error429);
+
+impl ComponentTrait for Error429 {
+    fn new() -> Self {
+        Self
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        PrepareMarkup::With(html! {
+            div {
+                h1 { ("TOO MANY REQUESTS") }
+                p { ("Please wait a moment before trying again.") }
+                (request_id(cx))
+            }
+        })
+    }
+}
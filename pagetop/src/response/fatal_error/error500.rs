@@ -0,0 +1,23 @@
+use crate::core::component::{ComponentTrait, Context};
+use crate::html::{html, PrepareMarkup};
+use crate::impl_handle;
+use crate::response::fatal_error::request_id;
+
+pub struct Error500;
+
+impl_handle!(ERROR_500 for Error500);
+
+impl ComponentTrait for Error500 {
+    fn new() -> Self {
+        Self
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        PrepareMarkup::With(html! {
+            div {
+                h1 { ("INTERNAL SERVER ERROR") }
+                (request_id(cx))
+            }
+        })
+    }
+}
@@ -0,0 +1,24 @@
+use crate::core::component::{ComponentTrait, Context};
+use crate::html::{html, PrepareMarkup};
+use crate::impl_handle;
+use crate::response::fatal_error::request_id;
+
+pub struct Error503;
+
+impl_handle!(ERROR_503 for Error503);
+
+impl ComponentTrait for Error503 {
+    fn new() -> Self {
+        Self
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        PrepareMarkup::With(html! {
+            div {
+                h1 { ("SITE UNDER MAINTENANCE") }
+                p { ("Please try again later.") }
+                (request_id(cx))
+            }
+        })
+    }
+}
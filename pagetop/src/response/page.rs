@@ -6,6 +6,7 @@ use crate::html::{html, Markup, DOCTYPE};
 use crate::html::{ClassesOp, Favicon, OptionClasses, OptionId, OptionTranslated};
 use crate::locale::L10n;
 use crate::response::fatal_error::FatalError;
+use crate::service::HttpMessage;
 use crate::{fn_builder, service};
 
 use unic_langid::CharacterDirection;
@@ -150,6 +151,11 @@ impl Page {
     // Page RENDER.
 
     pub fn render(&mut self) -> ResultPage<Markup, FatalError> {
+        // Module actions to decide whether the page can be accessed.
+        if !action::page::CheckPageAccess::dispatch(self) {
+            return Err(FatalError::AccessDenied(self.context.request().clone()));
+        }
+
         // Theme actions before preparing the page body.
         self.context.theme().before_prepare_body(self);
 
@@ -165,21 +171,32 @@ impl Page {
         // Module actions after preparing the page body.
         action::page::AfterPrepareBody::dispatch(self);
 
+        // Module actions before preparing the page head.
+        action::page::BeforePrepareHead::dispatch(self);
+
         // Prepare page head.
         let head = self.context.theme().prepare_head(self);
 
+        // Sources of scripts and styles for the Content-Security-Policy header.
+        let sources = self.context.content_sources();
+        self.context.request().extensions_mut().insert(sources);
+
         // Render the page.
         let lang = self.context.langid().language.as_str();
         let dir = match self.context.langid().character_direction() {
             CharacterDirection::LTR => "ltr",
             CharacterDirection::RTL => "rtl",
+            _ => "ltr",
         };
-        Ok(html! {
+        let markup = html! {
             (DOCTYPE)
             html lang=(lang) dir=(dir) {
                 (head)
                 (body)
             }
-        })
+        };
+
+        // Module actions to filter the rendered page.
+        Ok(action::page::AlterFinalMarkup::dispatch(self, markup))
     }
 }
@@ -12,6 +12,102 @@ pub use actix_web::{App, Error, HttpMessage, HttpRequest, HttpResponse, HttpServ
 pub use actix_web_files::Files as ActixFiles;
 pub use actix_web_static_files::ResourceFiles;
 
+mod access;
+pub use access::{check_permission, grant_permission, has_permission, revoke_permission};
+pub use access::{load_grants, set_grant_store, GrantStore};
+pub use access::{session_roles, set_session_roles};
+pub use access::{ADMINISTRATOR_ROLE, ANONYMOUS_ROLE, AUTHENTICATED_ROLE};
+
+mod csrf;
+pub use csrf::{check_csrf_token, csrf_token, CsrfForm, CSRF_FIELD, CSRF_HEADER};
+
+mod rate_limit;
+pub(crate) use rate_limit::check_rate_limit;
+pub use rate_limit::{add_rate_limit, FnRateLimitKey, RateLimit, RateLimitKey};
+
+mod request_id;
+pub(crate) use request_id::{add_request_id_header, assign_request_id, RequestIdRootSpan};
+pub use request_id::{RequestId, REQUEST_ID_HEADER};
+
+use crate::{Handle, LazyStatic};
+
+use std::cell::Cell;
+use std::sync::RwLock;
+
+// Método, ruta y módulo que la declara.
+type Route = (String, String, Option<Handle>);
+
+// Rutas declaradas por los módulos, para listarlas desde la CLI y para filtrar las acciones de las
+// peticiones por el módulo que declara la ruta.
+static ROUTES: LazyStatic<RwLock<Vec<Route>>> = LazyStatic::new(|| RwLock::new(Vec::new()));
+
+thread_local! {
+    // Módulo cuyos servicios se están configurando en este hilo.
+    static CONFIGURING_MODULE: Cell<Option<Handle>> = const { Cell::new(None) };
+}
+
+pub(crate) fn set_configuring_module(module: Option<Handle>) {
+    CONFIGURING_MODULE.with(|m| m.set(module));
+}
+
+#[doc(hidden)]
+pub fn add_route(method: &str, path: &str) {
+    let method = method.to_uppercase();
+    let mut routes = ROUTES.write().unwrap();
+    if !routes.iter().any(|(m, p, _)| *m == method && p == path) {
+        let module = CONFIGURING_MODULE.with(Cell::get);
+        routes.push((method, path.to_owned(), module));
+    }
+}
+
+pub(crate) fn routes() -> Vec<(String, String)> {
+    ROUTES
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(method, path, _)| (method.to_owned(), path.to_owned()))
+        .collect()
+}
+
+// Devuelve el identificador del módulo que declara la ruta `path`, si se conoce.
+pub(crate) fn route_module(path: &str) -> Option<Handle> {
+    ROUTES
+        .read()
+        .unwrap()
+        .iter()
+        .find(|(_, p, _)| p == path)
+        .and_then(|(_, _, module)| *module)
+}
+
+// Rutas desde las que se sirven archivos estáticos.
+static STATIC_FILES: LazyStatic<RwLock<Vec<String>>> = LazyStatic::new(|| RwLock::new(Vec::new()));
+
+#[doc(hidden)]
+pub fn add_static_files(path: &str) {
+    add_route("get", path);
+    let mut paths = STATIC_FILES.write().unwrap();
+    if !paths.iter().any(|p| p == path) {
+        paths.push(path.to_owned());
+    }
+}
+
+pub(crate) fn is_static_files(path: &str) -> bool {
+    STATIC_FILES
+        .read()
+        .unwrap()
+        .iter()
+        .any(|p| path_starts_with(path, p))
+}
+
+/// Comprueba si la ruta `path` es `prefix` o cuelga de ella.
+pub(crate) fn path_starts_with(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/') || prefix.is_empty(),
+        None => false,
+    }
+}
+
 #[macro_export]
 macro_rules! static_files {
     ( $bundle:ident ) => {
@@ -39,6 +135,7 @@ macro_rules! service_for_static_files {
         $crate::paste! {
             let span = $crate::trace::debug_span!("Configuring static files ", path = $path);
             let _ = span.in_scope(|| {
+                $crate::service::add_static_files($path);
                 let mut serve_embedded:bool = true;
                 $(
                     if !$root.is_empty() && !$relative.is_empty() {
@@ -61,3 +158,11 @@ macro_rules! service_for_static_files {
         }
     }};
 }
+
+#[macro_export]
+macro_rules! service_for_route {
+    ( $scfg:ident, $method:ident $path:literal => $handler:expr ) => {{
+        $crate::service::add_route(stringify!($method), $path);
+        $scfg.route($path, $crate::service::web::$method().to($handler));
+    }};
+}
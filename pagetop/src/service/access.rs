@@ -0,0 +1,150 @@
+use crate::core::module::all::is_permission;
+use crate::response::fatal_error::FatalError;
+use crate::service::{HttpRequest, Session};
+use crate::{trace, LazyStatic};
+
+use actix_session::SessionExt;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+/// Rol de los visitantes sin sesión iniciada.
+pub const ANONYMOUS_ROLE: &str = "anonymous";
+
+/// Rol de los usuarios con sesión iniciada.
+pub const AUTHENTICATED_ROLE: &str = "authenticated";
+
+/// Rol con todos los permisos.
+pub const ADMINISTRATOR_ROLE: &str = "administrator";
+
+// Clave de la sesión que guarda los roles del usuario.
+const ROLES_SESSION_KEY: &str = "roles";
+
+/// Almacén persistente de los permisos concedidos a cada rol.
+///
+/// Por defecto los permisos sólo se guardan en memoria y se pierden al reiniciar. Un módulo puede
+/// guardarlos, por ejemplo, en la base de datos asignando su almacén con [`set_grant_store()`],
+/// normalmente en [`init()`](crate::core::module::ModuleTrait::init).
+#[async_trait::async_trait(?Send)]
+pub trait GrantStore: Send + Sync {
+    /// Devuelve todos los pares `(rol, permiso)` guardados.
+    async fn load(&self) -> Result<Vec<(String, String)>, String>;
+
+    async fn grant(&self, role: &str, permission: &str) -> Result<(), String>;
+
+    async fn revoke(&self, role: &str, permission: &str) -> Result<(), String>;
+}
+
+static GRANT_STORE: LazyStatic<RwLock<Option<Arc<dyn GrantStore>>>> =
+    LazyStatic::new(|| RwLock::new(None));
+
+// Permisos concedidos a cada rol, cargados del almacén para consultarlos en cada petición.
+static GRANTS: LazyStatic<RwLock<HashMap<String, HashSet<String>>>> =
+    LazyStatic::new(|| RwLock::new(HashMap::new()));
+
+/// Asigna el almacén persistente de los permisos concedidos a cada rol.
+pub fn set_grant_store(store: impl GrantStore + 'static) {
+    *GRANT_STORE.write().unwrap() = Some(Arc::new(store));
+}
+
+fn grant_store() -> Option<Arc<dyn GrantStore>> {
+    GRANT_STORE.read().unwrap().clone()
+}
+
+/// Vuelve a cargar los permisos concedidos desde el almacén asignado con [`set_grant_store()`].
+///
+/// Con la base de datos habilitada se cargan al preparar la aplicación, después de aplicar las
+/// migraciones.
+pub async fn load_grants() -> Result<(), String> {
+    let store = match grant_store() {
+        Some(store) => store,
+        None => return Ok(()),
+    };
+    let mut grants: HashMap<String, HashSet<String>> = HashMap::new();
+    for (role, permission) in store.load().await?.into_iter() {
+        grants.entry(role).or_default().insert(permission);
+    }
+    *GRANTS.write().unwrap() = grants;
+    Ok(())
+}
+
+/// Concede a `role` el permiso `permission`, que debería haber sido declarado por algún módulo en
+/// [`permissions()`](crate::core::module::ModuleTrait::permissions). Se guarda en el almacén
+/// asignado con [`set_grant_store()`], si lo hay.
+pub async fn grant_permission(role: &str, permission: &str) -> Result<(), String> {
+    if !is_permission(permission) {
+        trace::warn!(
+            "Permission \"{}\" granted to role \"{}\" is not declared by any module",
+            permission,
+            role
+        );
+    }
+    if let Some(store) = grant_store() {
+        store.grant(role, permission).await?;
+    }
+    GRANTS
+        .write()
+        .unwrap()
+        .entry(role.to_owned())
+        .or_default()
+        .insert(permission.to_owned());
+    Ok(())
+}
+
+/// Retira a `role` el permiso `permission`, también del almacén asignado con [`set_grant_store()`].
+pub async fn revoke_permission(role: &str, permission: &str) -> Result<(), String> {
+    if let Some(store) = grant_store() {
+        store.revoke(role, permission).await?;
+    }
+    if let Some(permissions) = GRANTS.write().unwrap().get_mut(role) {
+        permissions.remove(permission);
+    }
+    Ok(())
+}
+
+/// Guarda en la sesión los roles del usuario, normalmente al iniciar sesión.
+pub fn set_session_roles(session: &Session, roles: &[&str]) {
+    if let Err(e) = session.insert(ROLES_SESSION_KEY, roles) {
+        trace::error!("Failed to save the roles in session ({})", e);
+    }
+}
+
+/// Devuelve los roles de la sesión de la petición, o [`ANONYMOUS_ROLE`] si no tiene ninguno.
+pub fn session_roles(request: &HttpRequest) -> Vec<String> {
+    match request.get_session().get::<Vec<String>>(ROLES_SESSION_KEY) {
+        Ok(Some(roles)) if !roles.is_empty() => roles,
+        _ => vec![ANONYMOUS_ROLE.to_owned()],
+    }
+}
+
+/// Indica si alguno de los roles de la sesión de la petición tiene el permiso `permission`. El rol
+/// [`ADMINISTRATOR_ROLE`] tiene todos los permisos.
+pub fn has_permission(request: &HttpRequest, permission: &str) -> bool {
+    let grants = GRANTS.read().unwrap();
+    session_roles(request).iter().any(|role| {
+        role == ADMINISTRATOR_ROLE
+            || grants
+                .get(role)
+                .is_some_and(|permissions| permissions.contains(permission))
+    })
+}
+
+/// Comprueba que la sesión de la petición tiene el permiso `permission`. Si no lo tiene devuelve
+/// [`FatalError::AccessDenied`].
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// async fn admin(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+///     service::check_permission(&request, "administer site")?;
+///     Page::new(request).render()
+/// }
+/// ```
+pub fn check_permission(request: &HttpRequest, permission: &str) -> Result<(), FatalError> {
+    if has_permission(request, permission) {
+        Ok(())
+    } else {
+        trace::warn!("Access denied, permission \"{}\" required", permission);
+        Err(FatalError::AccessDenied(request.clone()))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::response::fatal_error::FatalError;
+use crate::service::{self, web, HttpRequest};
+use crate::trace;
+
+use actix_session::SessionExt;
+use actix_web::dev::Payload;
+use actix_web::FromRequest;
+use rand::distributions::Alphanumeric;
+use rand::{rngs::OsRng, Rng};
+use serde::de::DeserializeOwned;
+
+use std::future::Future;
+use std::pin::Pin;
+
+/// Nombre del campo oculto de los formularios con el *token* anti-falsificación.
+pub const CSRF_FIELD: &str = "csrf_token";
+
+/// Cabecera alternativa para enviar el *token* anti-falsificación en peticiones sin formulario.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+// Clave de la sesión que guarda el token anti-falsificación.
+const CSRF_SESSION_KEY: &str = "csrf_token";
+
+/// Devuelve el *token* anti-falsificación de la sesión de la petición, creándolo si aún no existe.
+///
+/// El componente [`Form`](crate::base::component::Form) lo incluye automáticamente en los
+/// formularios que se envían con el método POST.
+pub fn csrf_token(request: &HttpRequest) -> String {
+    let session = request.get_session();
+    if let Ok(Some(token)) = session.get::<String>(CSRF_SESSION_KEY) {
+        return token;
+    }
+    let token: String = (0..32)
+        .map(|_| OsRng.sample(Alphanumeric) as char)
+        .collect();
+    if let Err(e) = session.insert(CSRF_SESSION_KEY, &token) {
+        trace::error!("Failed to save the CSRF token in session ({})", e);
+    }
+    token
+}
+
+/// Comprueba que `token` coincide con el *token* anti-falsificación de la sesión de la petición.
+///
+/// Si no coincide, o no se ha recibido, devuelve [`FatalError::AccessDenied`].
+pub fn check_csrf_token(request: &HttpRequest, token: Option<&str>) -> Result<(), FatalError> {
+    let expected = request
+        .get_session()
+        .get::<String>(CSRF_SESSION_KEY)
+        .ok()
+        .flatten();
+    match (expected, token) {
+        (Some(expected), Some(token)) if constant_time_eq(&expected, token) => Ok(()),
+        _ => {
+            trace::warn!("Rejected request with missing or invalid CSRF token");
+            Err(FatalError::AccessDenied(request.clone()))
+        }
+    }
+}
+
+// Compara sin terminar en la primera diferencia, para no dar pistas sobre el token por el tiempo de
+// respuesta.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Extractor de formularios protegidos contra falsificación de peticiones (CSRF).
+///
+/// Funciona como [`web::Form`], pero antes comprueba el *token* anti-falsificación recibido en el
+/// campo [`CSRF_FIELD`] del formulario o en la cabecera [`CSRF_HEADER`]. Si no es válido rechaza la
+/// petición con [`FatalError::AccessDenied`].
+///
+/// ```rust
+/// use pagetop::prelude::*;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Login {
+///     name: String,
+///     pass: String,
+/// }
+///
+/// async fn login(form: service::CsrfForm<Login>) -> String {
+///     format!("Hello, {}", form.name)
+/// }
+/// ```
+pub struct CsrfForm<T>(pub T);
+
+impl<T> CsrfForm<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> std::ops::Deref for CsrfForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for CsrfForm<T> {
+    type Error = service::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(request: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let request = request.clone();
+        let body = web::Bytes::from_request(&request, payload);
+        Box::pin(async move {
+            let body = body.await?;
+
+            let token = url::form_urlencoded::parse(&body)
+                .find(|(field, _)| field == CSRF_FIELD)
+                .map(|(_, value)| value.into_owned())
+                .or_else(|| {
+                    request
+                        .headers()
+                        .get(CSRF_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .map(str::to_owned)
+                });
+            check_csrf_token(&request, token.as_deref())?;
+
+            let mut payload = Payload::from(body);
+            let form = web::Form::<T>::from_request(&request, &mut payload).await?;
+            Ok(CsrfForm(form.into_inner()))
+        })
+    }
+}
@@ -0,0 +1,253 @@
+use crate::response::fatal_error::FatalError;
+use crate::service::http::header;
+use crate::service::{self, path_starts_with};
+use crate::{config, trace, LazyStatic};
+
+use actix_session::SessionExt;
+use rand::distributions::Alphanumeric;
+use rand::{rngs::OsRng, Rng};
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Clave de la sesión que identifica al cliente cuando el límite se aplica por sesión.
+const RATE_LIMIT_SESSION_KEY: &str = "rate_limit_key";
+
+// Número de contadores a partir del cual se eliminan los que ya se han recuperado por completo.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+pub type FnRateLimitKey = fn(&service::Request) -> Option<String>;
+
+/// Criterio para identificar al cliente al que se aplica un límite de peticiones.
+#[derive(Clone, Copy)]
+pub enum RateLimitKey {
+    /// Dirección IP del cliente.
+    ClientIp,
+    /// Sesión del cliente. Mientras la sesión no esté establecida se usa la dirección IP.
+    Session,
+    /// Clave propia obtenida de la petición. Si la función devuelve `None` se usa la dirección IP.
+    Custom(FnRateLimitKey),
+}
+
+/// Límite de peticiones con el algoritmo *token bucket*: cada cliente dispone de hasta `requests`
+/// peticiones seguidas, que se recuperan progresivamente en `seconds` segundos.
+///
+/// Los módulos pueden declarar límites para sus propias rutas en
+/// [`configure_service()`](crate::core::module::ModuleTrait::configure_service) con
+/// [`add_rate_limit()`]:
+///
+/// ```rust
+/// use pagetop::prelude::*;
+///
+/// struct User;
+///
+/// impl_handle!(MODULE_USER for User);
+///
+/// impl ModuleTrait for User {
+///     fn configure_service(&self, _scfg: &mut service::web::ServiceConfig) {
+///         service::add_rate_limit(
+///             "/user/login",
+///             service::RateLimit::new(5, 60).with_key(service::RateLimitKey::ClientIp),
+///         );
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct RateLimit {
+    requests: u32,
+    seconds: u64,
+    key: RateLimitKey,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, seconds: u64) -> Self {
+        RateLimit {
+            requests,
+            seconds,
+            key: RateLimitKey::ClientIp,
+        }
+    }
+
+    // RateLimit BUILDER.
+
+    pub fn with_key(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    // RateLimit GETTERS.
+
+    pub fn requests(&self) -> u32 {
+        self.requests
+    }
+
+    pub fn seconds(&self) -> u64 {
+        self.seconds
+    }
+
+    pub fn key(&self) -> RateLimitKey {
+        self.key
+    }
+
+    fn from_settings(route: &config::RateLimitRoute) -> Self {
+        let key = match route.key.trim().to_lowercase().as_str() {
+            "" | "ip" => RateLimitKey::ClientIp,
+            "session" => RateLimitKey::Session,
+            other => {
+                trace::warn!(
+                    "Rate limit key \"{}\" not valid. Using \"ip\". Check the settings file.",
+                    other
+                );
+                RateLimitKey::ClientIp
+            }
+        };
+        RateLimit::new(route.requests, route.seconds).with_key(key)
+    }
+}
+
+// Límites declarados por los módulos para cada prefijo de ruta.
+static RATE_LIMITS: LazyStatic<RwLock<Vec<(String, RateLimit)>>> =
+    LazyStatic::new(|| RwLock::new(Vec::new()));
+
+/// Declara un límite de peticiones para las rutas que empiezan por `prefix`.
+///
+/// Sustituye al límite declarado antes para el mismo prefijo. Los límites de la sección
+/// `[rate_limit]` de la configuración tienen preferencia sobre los declarados por los módulos.
+pub fn add_rate_limit(prefix: &str, limit: RateLimit) {
+    let mut limits = RATE_LIMITS.write().unwrap();
+    match limits.iter_mut().find(|(p, _)| p == prefix) {
+        Some((_, l)) => *l = limit,
+        None => limits.push((prefix.to_owned(), limit)),
+    }
+}
+
+// Límite del prefijo más largo que coincide con la ruta, con preferencia para la configuración.
+fn rate_limit_for(path: &str) -> Option<(String, RateLimit)> {
+    let configured = config::SETTINGS
+        .rate_limit
+        .routes
+        .iter()
+        .filter(|(prefix, _)| path_starts_with(path, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(prefix, route)| (prefix.to_owned(), RateLimit::from_settings(route)));
+    let declared = RATE_LIMITS
+        .read()
+        .unwrap()
+        .iter()
+        .filter(|(prefix, _)| path_starts_with(path, prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .cloned();
+    match (configured, declared) {
+        (Some(c), Some(d)) if d.0.len() > c.0.len() => Some(d),
+        (Some(c), _) => Some(c),
+        (None, d) => d,
+    }
+}
+
+// TOKEN BUCKETS ***********************************************************************************
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    refill: Duration,
+}
+
+static BUCKETS: LazyStatic<Mutex<HashMap<(String, String), Bucket>>> =
+    LazyStatic::new(|| Mutex::new(HashMap::new()));
+
+// Consume una petición del contador del cliente o devuelve los segundos que debe esperar.
+fn take(prefix: String, client: String, limit: &RateLimit) -> Result<(), u64> {
+    let capacity = limit.requests as f64;
+    let rate = capacity / limit.seconds.max(1) as f64;
+    let now = Instant::now();
+
+    let mut buckets = BUCKETS.lock().unwrap();
+    if buckets.len() >= SWEEP_THRESHOLD {
+        buckets.retain(|_, b| now.duration_since(b.updated) < b.refill);
+    }
+    let bucket = buckets.entry((prefix, client)).or_insert(Bucket {
+        tokens: capacity,
+        updated: now,
+        refill: Duration::from_secs(limit.seconds),
+    });
+    bucket.tokens =
+        (bucket.tokens + now.duration_since(bucket.updated).as_secs_f64() * rate).min(capacity);
+    bucket.updated = now;
+    if bucket.tokens >= 1.0 {
+        bucket.tokens -= 1.0;
+        Ok(())
+    } else {
+        Err(((1.0 - bucket.tokens) / rate).ceil() as u64)
+    }
+}
+
+// CLIENT KEYS *************************************************************************************
+
+fn client_key(request: &service::Request, key: RateLimitKey) -> String {
+    let custom = match key {
+        RateLimitKey::ClientIp => None,
+        RateLimitKey::Session => session_key(request).map(|k| format!("session:{}", k)),
+        RateLimitKey::Custom(f) => f(request).map(|k| format!("custom:{}", k)),
+    };
+    custom.unwrap_or_else(|| format!("ip:{}", client_ip(request)))
+}
+
+fn client_ip(request: &service::Request) -> String {
+    if config::SETTINGS.rate_limit.trust_proxy_headers {
+        if let Some(addr) = request.connection_info().realip_remote_addr() {
+            return match addr.parse::<SocketAddr>() {
+                Ok(addr) => addr.ip().to_string(),
+                Err(_) => addr.to_owned(),
+            };
+        }
+    }
+    request
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_default()
+}
+
+// Devuelve la clave guardada en la sesión o, si aún no existe, la crea para las siguientes
+// peticiones.
+fn session_key(request: &service::Request) -> Option<String> {
+    let session = request.get_session();
+    if let Ok(Some(key)) = session.get::<String>(RATE_LIMIT_SESSION_KEY) {
+        return Some(key);
+    }
+    let key: String = (0..22)
+        .map(|_| OsRng.sample(Alphanumeric) as char)
+        .collect();
+    if let Err(e) = session.insert(RATE_LIMIT_SESSION_KEY, key) {
+        trace::error!("Failed to save the rate limit key in session ({})", e);
+    }
+    None
+}
+
+// MIDDLEWARE **************************************************************************************
+
+/// Responde con la página del error 429 si el cliente supera el límite de peticiones de la ruta.
+pub(crate) fn check_rate_limit(
+    request: service::Request,
+) -> Result<service::Request, Box<service::Response>> {
+    if !config::SETTINGS.rate_limit.enabled {
+        return Ok(request);
+    }
+    let (prefix, limit) = match rate_limit_for(request.path()) {
+        Some((prefix, limit)) if limit.requests > 0 => (prefix, limit),
+        _ => return Ok(request),
+    };
+    let client = client_key(&request, limit.key);
+    match take(prefix, client, &limit) {
+        Ok(()) => Ok(request),
+        Err(retry_after) => {
+            let error = FatalError::TooManyRequests(request.request().clone());
+            let mut response = request.error_response(error);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, header::HeaderValue::from(retry_after));
+            Err(Box::new(response))
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::service::http::header::{HeaderName, HeaderValue};
+use crate::service::{self, HttpMessage, HttpRequest};
+
+use actix_web::body::MessageBody;
+use rand::distributions::Alphanumeric;
+use rand::{rngs::OsRng, Rng};
+use tracing::field::Empty;
+use tracing::Span;
+use tracing_actix_web::{DefaultRootSpanBuilder, RootSpanBuilder};
+
+use std::fmt;
+
+/// Cabecera con el identificador de la petición, que se reutiliza si la envía un *proxy* y se
+/// devuelve siempre en la respuesta.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+// Longitud máxima de un identificador recibido en la cabecera para reutilizarlo.
+const MAX_LENGTH: usize = 128;
+
+/// Identificador único de cada petición.
+///
+/// Se asigna al recibir la petición, reutilizando el de la cabecera `X-Request-Id` si es válido, y
+/// se mantiene en las trazas, en el contexto de renderizado ([`Context::request_id()`]), en las
+/// páginas de error y en la cabecera `X-Request-Id` de la respuesta.
+///
+/// [`Context::request_id()`]: crate::core::component::Context::request_id
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Devuelve el identificador asignado a la petición, si lo tiene.
+    pub fn of(request: &HttpRequest) -> Option<RequestId> {
+        request.extensions().get::<RequestId>().cloned()
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    fn from_header(value: &HeaderValue) -> Option<RequestId> {
+        let value = value.to_str().ok()?.trim();
+        let valid = !value.is_empty()
+            && value.len() <= MAX_LENGTH
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+        valid.then(|| RequestId(value.to_owned()))
+    }
+
+    fn generate() -> RequestId {
+        RequestId(
+            (0..22)
+                .map(|_| OsRng.sample(Alphanumeric) as char)
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Asigna a la petición su identificador, si aún no lo tiene, y lo devuelve.
+pub(crate) fn assign_request_id(request: &service::Request) -> RequestId {
+    if let Some(request_id) = request.extensions().get::<RequestId>() {
+        return request_id.clone();
+    }
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(RequestId::from_header)
+        .unwrap_or_else(RequestId::generate);
+    request.extensions_mut().insert(request_id.clone());
+    request_id
+}
+
+/// Añade el identificador de la petición a la respuesta si el servicio no lo ha hecho ya.
+pub(crate) fn add_request_id_header(response: &mut service::Response, request_id: &RequestId) {
+    let headers = response.headers_mut();
+    if !headers.contains_key(REQUEST_ID_HEADER) {
+        if let Ok(value) = HeaderValue::from_str(request_id.as_str()) {
+            headers.insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+        }
+    }
+}
+
+/// Intervalo raíz de las trazas de cada petición, con el identificador asignado a la petición en el
+/// campo `request_id`.
+pub(crate) struct RequestIdRootSpan;
+
+impl RootSpanBuilder for RequestIdRootSpan {
+    fn on_request_start(request: &service::Request) -> Span {
+        let request_id = assign_request_id(request);
+        let user_agent = request
+            .headers()
+            .get("User-Agent")
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("");
+        tracing::info_span!(
+            "HTTP request",
+            http.method = %request.method(),
+            http.target = %request.uri().path_and_query().map(|p| p.as_str()).unwrap_or(""),
+            http.client_ip = %request.connection_info().realip_remote_addr().unwrap_or(""),
+            http.user_agent = %user_agent,
+            http.status_code = Empty,
+            otel.status_code = Empty,
+            request_id = %request_id,
+            exception.message = Empty,
+            exception.details = Empty,
+        )
+    }
+
+    fn on_request_end<B: MessageBody>(
+        span: Span,
+        outcome: &Result<service::Response<B>, service::Error>,
+    ) {
+        DefaultRootSpanBuilder::on_request_end(span, outcome);
+    }
+}
@@ -23,6 +23,8 @@ pub use tracing::{debug_span, error_span, info_span, trace_span, warn_span};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::EnvFilter;
 
+use std::sync::Mutex;
+
 /// Registro de trazas y eventos de la aplicación.
 ///
 /// Para aumentar el rendimiento, un subproceso dedicado utiliza un sistema de escritura sin bloqueo
@@ -31,11 +33,12 @@ use tracing_subscriber::EnvFilter;
 /// std::process::exit), es posible que algunas trazas o eventos no se envíen.
 ///
 /// Puesto que las trazas o eventos registrados poco antes de la caída de una aplicación suelen ser
-/// importantes para diagnosticar la causa del fallo, con `Lazy<WorkerGuard>` se garantiza que todos
-/// los registros almacenados se enviarán antes de terminar la ejecución.
+/// importantes para diagnosticar la causa del fallo, el [`WorkerGuard`] se conserva hasta que la
+/// aplicación termina ordenadamente y se libera con [`shutdown()`], garantizando que todos los
+/// registros almacenados se envíen antes de terminar la ejecución.
 
 #[rustfmt::skip]
-pub(crate) static TRACING: LazyStatic<WorkerGuard> = LazyStatic::new(|| {
+pub(crate) static TRACING: LazyStatic<Mutex<Option<WorkerGuard>>> = LazyStatic::new(|| {
     let env_filter =
         EnvFilter::try_new(&config::SETTINGS.log.tracing).unwrap_or_else(|_| EnvFilter::new("Info"));
 
@@ -65,7 +68,7 @@ pub(crate) static TRACING: LazyStatic<WorkerGuard> = LazyStatic::new(|| {
         .with_writer(non_blocking)
         .with_ansi(rolling.as_str() == "stdout");
     match config::SETTINGS.log.format.to_lowercase().as_str() {
-        "json"    => subscriber.json().init(),
+        "json"    => subscriber.json().with_current_span(true).init(),
         "full"    => subscriber.init(),
         "compact" => subscriber.compact().init(),
         "pretty"  => subscriber.pretty().init(),
@@ -78,5 +81,13 @@ pub(crate) static TRACING: LazyStatic<WorkerGuard> = LazyStatic::new(|| {
         }
     }
 
-    guard
+    Mutex::new(Some(guard))
 });
+
+/// Envía las trazas y eventos pendientes y cierra el registro. Las trazas o eventos posteriores se
+/// descartan.
+pub(crate) fn shutdown() {
+    if let Some(guard) = TRACING.lock().unwrap().take() {
+        drop(guard);
+    }
+}
@@ -1,9 +1,11 @@
 //! Functions and macro helpers.
 
-use crate::{trace, Handle};
+use crate::{trace, Handle, LazyStatic};
 
+use std::collections::HashMap;
 use std::io;
 use std::path::PathBuf;
+use std::sync::RwLock;
 
 // *************************************************************************************************
 // FUNCTIONS HELPERS.
@@ -59,6 +61,60 @@ pub fn single_type_name<T: ?Sized>() -> &'static str {
     partial_type_name(std::any::type_name::<T>(), 1)
 }
 
+/// Devuelve el nombre del tipo sin las rutas de los módulos, incluidos los parámetros genéricos. Por
+/// ejemplo, `OnEvent<UserLoggedIn>` para `pagetop::core::action::OnEvent<user::UserLoggedIn>`.
+pub fn short_type_name(type_name: &str) -> String {
+    let mut name = String::with_capacity(type_name.len());
+    let mut path = String::new();
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            path.push(c);
+        } else {
+            name.push_str(path.rsplit("::").next().unwrap_or_default());
+            path.clear();
+            name.push(c);
+        }
+    }
+    name.push_str(path.rsplit("::").next().unwrap_or_default());
+    name
+}
+
+// Tipos de los elementos registrados, sin parámetros genéricos, por su identificador.
+static HANDLE_NAMES: LazyStatic<RwLock<HashMap<Handle, &'static str>>> =
+    LazyStatic::new(|| RwLock::new(HashMap::new()));
+
+// Asocia el identificador al tipo del elemento. Los tipos genéricos comparten el mismo
+// identificador, así que se guardan sin sus parámetros. Si el identificador ya corresponde a otro
+// tipo, devuelve el nombre de ese tipo.
+pub(crate) fn register_handle_name(
+    handle: Handle,
+    type_name: &'static str,
+) -> Result<(), &'static str> {
+    let type_name = type_name.split('<').next().unwrap_or_default();
+    let mut names = HANDLE_NAMES.write().unwrap();
+    match names.get(&handle) {
+        Some(other) if *other != type_name => Err(other),
+        Some(_) => Ok(()),
+        None => {
+            names.insert(handle, type_name);
+            Ok(())
+        }
+    }
+}
+
+/// Devuelve el nombre legible del tipo al que corresponde el identificador `handle`, si se conoce.
+///
+/// Al preparar la aplicación se registran los identificadores de los módulos y temas habilitados,
+/// de los componentes añadidos a las regiones, de las acciones y de los elementos a los que se
+/// refieren las acciones (por ejemplo, el componente o el evento).
+pub fn handle_name(handle: Handle) -> Option<String> {
+    HANDLE_NAMES
+        .read()
+        .unwrap()
+        .get(&handle)
+        .map(|type_name| short_type_name(type_name))
+}
+
 pub fn absolute_dir(
     root_path: impl Into<String>,
     relative_path: impl Into<String>,
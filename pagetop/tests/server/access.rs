@@ -0,0 +1,75 @@
+use pagetop::prelude::*;
+
+struct Access;
+
+impl_handle!(MODULE_TEST_SERVER_ACCESS for Access);
+
+impl ModuleTrait for Access {
+    fn permissions(&self) -> Vec<Permission> {
+        vec![Permission::new("access reports")]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/login/{role}" => login);
+        service_for_route!(scfg, get "/reports" => reports);
+    }
+}
+
+async fn login(session: service::Session, role: service::web::Path<String>) -> &'static str {
+    service::set_session_roles(&session, &[role.as_str()]);
+    "Logged in"
+}
+
+async fn reports(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    service::check_permission(&request, "access reports")?;
+    Ok(html! { "Reports" })
+}
+
+#[pagetop::test]
+async fn access_denied_without_permission() {
+    let app = service::test::init_service(Application::prepare(&Access).unwrap().test()).await;
+    let req = service::test::TestRequest::get()
+        .uri("/reports")
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), service::http::StatusCode::FORBIDDEN);
+}
+
+// Inicia una sesión con el rol indicado y consulta los informes.
+macro_rules! reports_as {
+    ( $app:ident, $role:literal ) => {{
+        let req = service::test::TestRequest::get()
+            .uri(concat!("/login/", $role))
+            .to_request();
+        let resp = service::test::call_service(&$app, req).await;
+        let cookie = resp.response().cookies().next().unwrap().into_owned();
+        let req = service::test::TestRequest::get()
+            .uri("/reports")
+            .cookie(cookie)
+            .to_request();
+        service::test::call_service(&$app, req).await
+    }};
+}
+
+#[pagetop::test]
+async fn access_allowed_with_granted_permission() {
+    let app = service::test::init_service(Application::prepare(&Access).unwrap().test()).await;
+    service::grant_permission("reporter", "access reports")
+        .await
+        .unwrap();
+
+    let resp = reports_as!(app, "reporter");
+    assert_eq!(resp.status(), service::http::StatusCode::OK);
+
+    let resp = reports_as!(app, "editor");
+    assert_eq!(resp.status(), service::http::StatusCode::FORBIDDEN);
+}
+
+#[pagetop::test]
+async fn administrator_has_all_permissions() {
+    let app = service::test::init_service(Application::prepare(&Access).unwrap().test()).await;
+    let resp = reports_as!(app, "administrator");
+
+    assert_eq!(resp.status(), service::http::StatusCode::OK);
+}
@@ -0,0 +1,83 @@
+use pagetop::prelude::*;
+
+struct ComponentActions;
+
+impl_handle!(MODULE_TEST_SERVER_COMPONENT_ACTIONS for ComponentActions);
+
+impl ModuleTrait for ComponentActions {
+    fn actions(&self) -> Vec<Action> {
+        // Las acciones filtradas por el identificador del bloque se intercalan por peso con las que
+        // sólo lo están por el tipo de componente.
+        actions![
+            action::component::AlterComponentMarkup::<Block>::with(any_3).with_weight(3),
+            action::component::AlterComponentMarkup::<Block>::with(id_1)
+                .filter_by_referer_id("component-actions")
+                .with_weight(1),
+            action::component::AlterComponentMarkup::<Block>::with(any_2).with_weight(2),
+            action::component::AlterComponentMarkup::<Block>::with(id_4)
+                .filter_by_referer_id("component-actions")
+                .with_weight(4),
+            action::component::AlterComponentMarkup::<Block>::with(id_other)
+                .filter_by_referer_id("component-actions-other"),
+        ]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/component-actions" => component_actions);
+    }
+}
+
+async fn component_actions(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request)
+        .with_in(
+            "content",
+            Block::new()
+                .with_id("component-actions")
+                .add_component(Html::with(html! { "block" })),
+        )
+        .render()
+}
+
+// Añade `text` al código del bloque sólo en las peticiones de esta prueba, porque las acciones sin
+// filtro por identificador también se ejecutan para los bloques de otras pruebas.
+fn append(cx: &mut Context, markup: &Markup, text: &str) -> ActionResult<Markup> {
+    match cx.request().path() {
+        "/component-actions" => ActionResult::Replace(html! { (markup) (text) }),
+        _ => ActionResult::Continue,
+    }
+}
+
+fn id_1(_block: &Block, cx: &mut Context, markup: &Markup) -> ActionResult<Markup> {
+    append(cx, markup, "[id 1]")
+}
+
+fn any_2(_block: &Block, cx: &mut Context, markup: &Markup) -> ActionResult<Markup> {
+    append(cx, markup, "[any 2]")
+}
+
+fn any_3(_block: &Block, cx: &mut Context, markup: &Markup) -> ActionResult<Markup> {
+    append(cx, markup, "[any 3]")
+}
+
+fn id_4(_block: &Block, cx: &mut Context, markup: &Markup) -> ActionResult<Markup> {
+    append(cx, markup, "[id 4]")
+}
+
+fn id_other(_block: &Block, cx: &mut Context, markup: &Markup) -> ActionResult<Markup> {
+    append(cx, markup, "[other]")
+}
+
+#[pagetop::test]
+async fn component_markup_actions_run_by_weight_across_keys() {
+    let app =
+        service::test::init_service(Application::prepare(&ComponentActions).unwrap().test()).await;
+
+    let req = service::test::TestRequest::get()
+        .uri("/component-actions")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(body.contains("</div>[id 1][any 2][any 3][id 4]"));
+    assert!(!body.contains("[other]"));
+}
@@ -0,0 +1,86 @@
+use pagetop::prelude::*;
+
+use serde::Deserialize;
+
+struct Csrf;
+
+impl_handle!(MODULE_TEST_SERVER_CSRF for Csrf);
+
+impl ModuleTrait for Csrf {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/csrf" => form);
+        service_for_route!(scfg, post "/csrf" => submit);
+    }
+}
+
+#[derive(Deserialize)]
+struct Message {
+    text: String,
+}
+
+async fn form(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request)
+        .with_in("content", Form::new().with_action("/csrf"))
+        .render()
+}
+
+async fn submit(form: service::CsrfForm<Message>) -> String {
+    form.into_inner().text
+}
+
+// Abre una sesión nueva con la página del formulario y devuelve su cookie y el token incluido en
+// el formulario.
+macro_rules! rendered_token {
+    ( $app:ident ) => {{
+        let req = service::test::TestRequest::get().uri("/csrf").to_request();
+        let resp = service::test::call_service(&$app, req).await;
+        let cookie = resp.response().cookies().next().unwrap().into_owned();
+        let body = String::from_utf8(service::test::read_body(resp).await.to_vec()).unwrap();
+        let field = "name=\"csrf_token\" value=\"";
+        let start = body.find(field).unwrap() + field.len();
+        let token = body[start..].split('"').next().unwrap().to_owned();
+        (cookie, token)
+    }};
+}
+
+#[pagetop::test]
+async fn csrf_rejects_missing_token() {
+    let app = service::test::init_service(Application::prepare(&Csrf).unwrap().test()).await;
+    let req = service::test::TestRequest::post()
+        .uri("/csrf")
+        .set_form([("text", "hello")])
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), service::http::StatusCode::FORBIDDEN);
+}
+
+#[pagetop::test]
+async fn csrf_accepts_token_of_the_session() {
+    let app = service::test::init_service(Application::prepare(&Csrf).unwrap().test()).await;
+    let (cookie, token) = rendered_token!(app);
+    let req = service::test::TestRequest::post()
+        .uri("/csrf")
+        .cookie(cookie)
+        .set_form([("text", "hello"), (service::CSRF_FIELD, token.as_str())])
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), service::http::StatusCode::OK);
+    assert_eq!(service::test::read_body(resp).await, "hello");
+}
+
+#[pagetop::test]
+async fn csrf_rejects_token_of_another_session() {
+    let app = service::test::init_service(Application::prepare(&Csrf).unwrap().test()).await;
+    let (_, token) = rendered_token!(app);
+    let (cookie, _) = rendered_token!(app);
+    let req = service::test::TestRequest::post()
+        .uri("/csrf")
+        .cookie(cookie)
+        .set_form([("text", "hello"), (service::CSRF_FIELD, token.as_str())])
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), service::http::StatusCode::FORBIDDEN);
+}
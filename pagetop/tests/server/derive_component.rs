@@ -0,0 +1,105 @@
+use pagetop::prelude::*;
+
+#[derive(Component, Default)]
+#[component(handle = COMPONENT_TEST_SERVER_NOTE)]
+struct Note {
+    #[component(id)]
+    id: OptionId,
+    #[component(weight)]
+    weight: Weight,
+    #[component(renderable)]
+    renderable: Renderable,
+    #[component(classes)]
+    classes: OptionClasses,
+    #[component(template)]
+    template: String,
+    text: String,
+}
+
+#[component_trait]
+impl ComponentTrait for Note {
+    fn new() -> Self {
+        Note::default().with_classes(ClassesOp::Add, "note")
+    }
+
+    fn prepare_component(&self, _cx: &mut Context) -> PrepareMarkup {
+        PrepareMarkup::With(html! {
+            p id=[self.id()] class=[self.classes().get()] data-template=(self.template()) {
+                (self.text)
+            }
+        })
+    }
+}
+
+impl Note {
+    fn with_text(mut self, text: &str) -> Self {
+        self.text = text.to_owned();
+        self
+    }
+}
+
+// Sin identificador, peso ni condición para renderizar, y con `new()` generado.
+#[derive(Component, Default)]
+struct Separator {
+    #[component(classes)]
+    classes: OptionClasses,
+}
+
+#[component_trait]
+impl ComponentTrait for Separator {
+    fn prepare_component(&self, _cx: &mut Context) -> PrepareMarkup {
+        PrepareMarkup::With(html! { hr class=[self.classes().get()]; })
+    }
+}
+
+struct DeriveComponent;
+
+impl_handle!(MODULE_TEST_SERVER_DERIVE_COMPONENT for DeriveComponent);
+
+impl ModuleTrait for DeriveComponent {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/notes" => notes);
+    }
+}
+
+async fn notes(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    let mut second = Note::new().with_id("second").with_text("Second");
+    second
+        .alter_weight(-1)
+        .alter_classes(ClassesOp::Add, "highlighted")
+        .alter_template("compact");
+
+    Page::new(request)
+        .with_in("content", Note::new().with_id("first").with_text("First"))
+        .with_in("content", second)
+        .with_in(
+            "content",
+            Separator::new().with_classes(ClassesOp::Add, "separator"),
+        )
+        .with_in(
+            "content",
+            Note::new().with_text("Hidden").with_renderable(|_| false),
+        )
+        .render()
+}
+
+#[pagetop::test]
+async fn derived_component_renders_with_generated_builders() {
+    let app =
+        service::test::init_service(Application::prepare(&DeriveComponent).unwrap().test()).await;
+
+    let req = service::test::TestRequest::get().uri("/notes").to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    let first = r#"<p id="first" class="note" data-template="">First</p>"#;
+    let second = r#"<p id="second" class="note highlighted" data-template="compact">Second</p>"#;
+    assert!(body.contains(first));
+    assert!(body.contains(second));
+    assert!(body.find(second) < body.find(first));
+    assert!(body.contains(r#"<hr class="separator">"#));
+    assert_eq!(Separator::new().id(), None);
+    assert_eq!(Separator::new().weight(), 0);
+    assert!(!body.contains("Hidden"));
+    assert_eq!(Note::static_handle(), COMPONENT_TEST_SERVER_NOTE);
+}
@@ -0,0 +1,65 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+
+static CALLS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+#[derive(Clone)]
+struct Published;
+
+impl_handle!(EVENT_TEST_PUBLISHED for Published);
+
+impl EventTrait for Published {}
+
+struct Events;
+
+impl_handle!(MODULE_TEST_SERVER_EVENTS for Events);
+
+impl ModuleTrait for Events {
+    fn actions(&self) -> Vec<Action> {
+        actions![
+            OnEvent::<Published>::with(last).with_weight(10),
+            OnEvent::<Published>::with(failing).with_weight(2),
+            OnEvent::<Published>::with_async(panicking).with_weight(4),
+            OnEvent::<Published>::with(second),
+            OnEvent::<Published>::with_async(first).with_weight(-5),
+        ]
+    }
+}
+
+fn first(_event: Published) -> EventFuture {
+    Box::pin(async move {
+        CALLS.lock().unwrap().push("first");
+        Ok(())
+    })
+}
+
+fn second(_event: &Published) -> Result<(), String> {
+    CALLS.lock().unwrap().push("second");
+    Ok(())
+}
+
+fn failing(_event: &Published) -> Result<(), String> {
+    Err("failing subscriber".to_owned())
+}
+
+fn panicking(_event: Published) -> EventFuture {
+    Box::pin(async move { panic!("panicking subscriber") })
+}
+
+fn last(_event: &Published) -> Result<(), String> {
+    CALLS.lock().unwrap().push("last");
+    Ok(())
+}
+
+#[pagetop::test]
+async fn subscribers_run_by_weight_despite_failures() {
+    // Preparar de nuevo la aplicación no vuelve a registrar los suscriptores.
+    Application::prepare(&Events).unwrap();
+    let _app = service::test::init_service(Application::prepare(&Events).unwrap().test()).await;
+
+    let failed = publish_event(&Published).await;
+
+    assert_eq!(failed, 2);
+    assert_eq!(*CALLS.lock().unwrap(), ["first", "second", "last"]);
+}
@@ -0,0 +1,48 @@
+use pagetop::prelude::*;
+
+struct First;
+
+impl_handle!(MODULE_TEST_SERVER_FIRST for First);
+
+impl ModuleTrait for First {}
+
+// Otro tipo con el mismo identificador que `First`.
+struct Second;
+
+impl HasHandle for Second {
+    fn static_handle() -> Handle {
+        MODULE_TEST_SERVER_FIRST
+    }
+
+    fn handle(&self) -> Handle {
+        MODULE_TEST_SERVER_FIRST
+    }
+}
+
+impl ModuleTrait for Second {}
+
+struct Handles;
+
+impl_handle!(MODULE_TEST_SERVER_HANDLES for Handles);
+
+impl ModuleTrait for Handles {
+    fn dependencies(&self) -> Vec<ModuleRef> {
+        vec![&First, &Second]
+    }
+}
+
+#[pagetop::test]
+async fn shared_handle_is_rejected() {
+    match Application::prepare(&Handles) {
+        Err(PrepareError::HandleCollision {
+            handle,
+            element,
+            other,
+        }) => {
+            assert_eq!(handle, MODULE_TEST_SERVER_FIRST);
+            assert!(element.ends_with("::Second"));
+            assert!(other.ends_with("::First"));
+        }
+        _ => panic!("expected a handle collision"),
+    }
+}
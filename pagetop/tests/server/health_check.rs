@@ -9,8 +9,36 @@ impl ModuleTrait for HealthCheck {}
 #[pagetop::test]
 async fn health_check_works() {
     let app = service::test::init_service(Application::prepare(&HealthCheck).unwrap().test()).await;
-    let req = service::test::TestRequest::get().uri("/").to_request();
-    let _resp = service::test::call_service(&app, req).await;
+    let req = service::test::TestRequest::get()
+        .uri("/health")
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
 
-    //  assert_eq!("OK", "OK");
+    assert!(resp.status().is_success());
+    let body = service::test::read_body(resp).await;
+    assert_eq!(body, r#"{"status":"up"}"#);
+}
+
+#[pagetop::test]
+async fn readiness_check_works() {
+    let app = service::test::init_service(Application::prepare(&HealthCheck).unwrap().test()).await;
+    let req = service::test::TestRequest::get().uri("/ready").to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert!(resp.status().is_success());
+}
+
+#[pagetop::test]
+async fn request_id_is_returned() {
+    let app = service::test::init_service(Application::prepare(&HealthCheck).unwrap().test()).await;
+    let req = service::test::TestRequest::get()
+        .uri("/health")
+        .insert_header((service::REQUEST_ID_HEADER, "proxy-id-1234"))
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(
+        resp.headers().get(service::REQUEST_ID_HEADER).unwrap(),
+        "proxy-id-1234"
+    );
 }
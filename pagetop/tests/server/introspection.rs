@@ -0,0 +1,41 @@
+use pagetop::prelude::*;
+
+#[derive(Clone)]
+struct Pinged;
+
+impl_handle!(EVENT_TEST_PINGED for Pinged);
+
+impl EventTrait for Pinged {}
+
+struct Introspection;
+
+impl_handle!(MODULE_TEST_SERVER_INTROSPECTION for Introspection);
+
+impl ModuleTrait for Introspection {
+    fn actions(&self) -> Vec<Action> {
+        actions![OnEvent::<Pinged>::with(on_pinged).with_weight(5)]
+    }
+}
+
+fn on_pinged(_event: &Pinged) -> Result<(), String> {
+    Ok(())
+}
+
+#[pagetop::test]
+async fn registered_actions_are_listed() {
+    let _app =
+        service::test::init_service(Application::prepare(&Introspection).unwrap().test()).await;
+
+    let action = registered_actions()
+        .into_iter()
+        .find(|a| a.module() == "Introspection")
+        .expect("action of module Introspection not registered");
+
+    assert_eq!(action.name(), "OnEvent");
+    assert_eq!(action.referer_name().as_deref(), Some("Pinged"));
+    assert_eq!(action.weight(), 5);
+    assert_eq!(
+        util::handle_name(MODULE_TEST_SERVER_INTROSPECTION).as_deref(),
+        Some("Introspection")
+    );
+}
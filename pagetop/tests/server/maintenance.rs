@@ -0,0 +1,98 @@
+// El modo mantenimiento afecta a todo el proceso, por eso estas pruebas se compilan en su propio
+// ejecutable (ver `[[test]]` en Cargo.toml) y no como parte de `server`.
+
+use pagetop::prelude::*;
+
+use pagetop::app;
+use pagetop::service::http::{header, StatusCode};
+
+use std::fs;
+
+struct Maintenance;
+
+impl_handle!(MODULE_TEST_SERVER_MAINTENANCE for Maintenance);
+
+impl ModuleTrait for Maintenance {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/maintenance" => ok);
+        service_for_route!(scfg, get "/admin/maintenance" => ok);
+        service_for_route!(scfg, get "/admin/login" => login);
+    }
+}
+
+async fn ok() -> &'static str {
+    "ok"
+}
+
+async fn login(session: service::Session) -> &'static str {
+    app::allow_in_maintenance(&session);
+    "ok"
+}
+
+// Crea el archivo indicador del modo mantenimiento y lo elimina al terminar, aunque falle la prueba.
+struct FlagFile;
+
+impl FlagFile {
+    fn create() -> Self {
+        fs::write(&config::SETTINGS.server.maintenance_flag_file, b"").unwrap();
+        FlagFile
+    }
+}
+
+impl Drop for FlagFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&config::SETTINGS.server.maintenance_flag_file);
+    }
+}
+
+macro_rules! get {
+    ( $app:ident, $uri:expr ) => {
+        service::test::call_service(
+            &$app,
+            service::test::TestRequest::get().uri($uri).to_request(),
+        )
+        .await
+    };
+    ( $app:ident, $uri:expr, $cookie:expr ) => {
+        service::test::call_service(
+            &$app,
+            service::test::TestRequest::get()
+                .uri($uri)
+                .cookie($cookie)
+                .to_request(),
+        )
+        .await
+    };
+}
+
+#[pagetop::test]
+async fn maintenance_mode_works() {
+    let _flag = FlagFile::create();
+    let app = service::test::init_service(Application::prepare(&Maintenance).unwrap().test()).await;
+    assert!(app::is_in_maintenance());
+
+    // Las rutas no permitidas responden con un error 503 y la cabecera Retry-After.
+    let resp = get!(app, "/maintenance");
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        resp.headers().get(header::RETRY_AFTER).unwrap(),
+        config::SETTINGS
+            .server
+            .maintenance_retry_after
+            .to_string()
+            .as_str()
+    );
+
+    // Las rutas permitidas y las de comprobación del estado siguen disponibles.
+    assert_eq!(get!(app, "/admin/maintenance").status(), StatusCode::OK);
+    assert_eq!(
+        get!(app, config::SETTINGS.server.health_path.as_str()).status(),
+        StatusCode::OK
+    );
+
+    // Las sesiones autorizadas pueden navegar por todo el sitio.
+    let resp = get!(app, "/admin/login");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let cookie = resp.response().cookies().next().unwrap().into_owned();
+    assert_eq!(get!(app, "/maintenance", cookie).status(), StatusCode::OK);
+}
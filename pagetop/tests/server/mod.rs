@@ -1 +1,12 @@
+mod access;
+mod component_actions;
+mod csrf;
+mod derive_component;
+mod events;
+mod handles;
 mod health_check;
+mod introspection;
+mod page_actions;
+mod rate_limit;
+mod render_cache;
+mod security;
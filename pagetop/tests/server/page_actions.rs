@@ -0,0 +1,187 @@
+use pagetop::prelude::*;
+
+use std::sync::Mutex;
+
+// Las acciones sin filtro también se ejecutan para las peticiones de otras pruebas, por eso sólo
+// registran las de la ruta "/actions".
+static CALLS: Mutex<Vec<&str>> = Mutex::new(Vec::new());
+
+fn call(request: &service::HttpRequest, name: &'static str) {
+    if request.path() == "/actions" {
+        CALLS.lock().unwrap().push(name);
+    }
+}
+
+struct PageActions;
+
+impl_handle!(MODULE_TEST_SERVER_PAGE_ACTIONS for PageActions);
+
+impl ModuleTrait for PageActions {
+    fn actions(&self) -> Vec<Action> {
+        // En cada acción la filtrada pesa menos que la que no lo está, y debe ejecutarse antes. Las
+        // filtradas por el módulo que declara la ruta se intercalan por peso, y las filtradas por
+        // otro módulo no se ejecutan.
+        actions![
+            action::request::OnRequestStart::with(request_start_any).with_weight(1),
+            action::request::OnRequestStart::with(request_start_route)
+                .filter_by_referer_id("/actions")
+                .with_weight(-1),
+            action::request::OnRequestStart::with(request_start_module)
+                .filter_by_referer_handle(MODULE_TEST_SERVER_PAGE_ACTIONS),
+            action::request::OnRequestStart::with(request_start_other)
+                .filter_by_referer_handle(theme::THEME_BASIC),
+            action::page::CheckPageAccess::with(check_access_any).with_weight(1),
+            action::page::CheckPageAccess::with(check_access_template)
+                .filter_by_referer_id("default")
+                .with_weight(-1),
+            action::page::BeforePrepareHead::with(prepare_head_any).with_weight(1),
+            action::page::BeforePrepareHead::with(prepare_head_template)
+                .filter_by_referer_id("default")
+                .with_weight(-1),
+            action::page::AlterRegionComponents::with(region_any).with_weight(1),
+            action::page::AlterRegionComponents::with(region_content)
+                .filter_by_referer_id("content")
+                .with_weight(-1),
+            action::page::AlterFinalMarkup::with(final_markup_any).with_weight(1),
+            action::page::AlterFinalMarkup::with(final_markup_template)
+                .filter_by_referer_id("default")
+                .with_weight(-1),
+            action::request::OnResponseFinalize::with(response_finalize_any).with_weight(1),
+            action::request::OnResponseFinalize::with(response_finalize_route)
+                .filter_by_referer_id("/actions")
+                .with_weight(-1),
+            action::request::OnResponseFinalize::with(response_finalize_module)
+                .filter_by_referer_handle(MODULE_TEST_SERVER_PAGE_ACTIONS)
+                .with_weight(2),
+            action::request::OnResponseFinalize::with(response_finalize_other)
+                .filter_by_referer_handle(theme::THEME_BASIC),
+        ]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/actions" => actions);
+    }
+}
+
+async fn actions(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request).render()
+}
+
+fn request_start_any(request: &service::HttpRequest) {
+    call(request, "request start");
+}
+
+fn request_start_route(request: &service::HttpRequest) {
+    call(request, "request start /actions");
+}
+
+fn request_start_module(request: &service::HttpRequest) {
+    call(request, "request start module");
+}
+
+fn request_start_other(request: &service::HttpRequest) {
+    call(request, "request start other module");
+}
+
+fn check_access_any(page: &mut Page, _access: &bool) -> ActionResult<bool> {
+    call(page.context().request(), "check access");
+    ActionResult::Continue
+}
+
+fn check_access_template(page: &mut Page, _access: &bool) -> ActionResult<bool> {
+    call(page.context().request(), "check access default");
+    ActionResult::Continue
+}
+
+fn prepare_head_any(page: &mut Page) {
+    call(page.context().request(), "prepare head");
+}
+
+fn prepare_head_template(page: &mut Page) {
+    call(page.context().request(), "prepare head default");
+}
+
+fn region_any(components: &mut ArcComponents, cx: &mut Context) {
+    if cx.request().path() == "/actions" {
+        components.alter(ArcOp::Add(ArcComponent::with(Html::with(
+            html! { "[any]" },
+        ))));
+    }
+}
+
+fn region_content(components: &mut ArcComponents, _cx: &mut Context) {
+    components.alter(ArcOp::Add(ArcComponent::with(Html::with(
+        html! { "[content]" },
+    ))));
+}
+
+fn final_markup_any(page: &mut Page, markup: Markup) -> Markup {
+    match page.context().request().path() {
+        "/actions" => html! { (markup) "[any]" },
+        _ => markup,
+    }
+}
+
+fn final_markup_template(page: &mut Page, markup: Markup) -> Markup {
+    match page.context().request().path() {
+        "/actions" => html! { (markup) "[default]" },
+        _ => markup,
+    }
+}
+
+fn response_finalize_any(
+    request: &service::HttpRequest,
+    _headers: &mut service::http::header::HeaderMap,
+) {
+    call(request, "response finalize");
+}
+
+fn response_finalize_route(
+    request: &service::HttpRequest,
+    _headers: &mut service::http::header::HeaderMap,
+) {
+    call(request, "response finalize /actions");
+}
+
+fn response_finalize_module(
+    request: &service::HttpRequest,
+    _headers: &mut service::http::header::HeaderMap,
+) {
+    call(request, "response finalize module");
+}
+
+fn response_finalize_other(
+    request: &service::HttpRequest,
+    _headers: &mut service::http::header::HeaderMap,
+) {
+    call(request, "response finalize other module");
+}
+
+#[pagetop::test]
+async fn filtered_and_unfiltered_actions_run_by_weight() {
+    let app = service::test::init_service(Application::prepare(&PageActions).unwrap().test()).await;
+
+    let req = service::test::TestRequest::get()
+        .uri("/actions")
+        .to_request();
+    let body = service::test::call_and_read_body(&app, req).await;
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(body.replace(' ', "").contains("[content][any]"));
+    assert!(body.ends_with("[default][any]"));
+    assert_eq!(
+        *CALLS.lock().unwrap(),
+        [
+            "request start /actions",
+            "request start module",
+            "request start",
+            "check access default",
+            "check access",
+            "prepare head default",
+            "prepare head",
+            "response finalize /actions",
+            "response finalize",
+            "response finalize module",
+        ]
+    );
+}
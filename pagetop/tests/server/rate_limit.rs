@@ -0,0 +1,37 @@
+use pagetop::prelude::*;
+
+struct RateLimited;
+
+impl_handle!(MODULE_TEST_SERVER_RATE_LIMIT for RateLimited);
+
+impl ModuleTrait for RateLimited {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/limited" => limited);
+        service::add_rate_limit("/limited", service::RateLimit::new(2, 60));
+    }
+}
+
+async fn limited() -> &'static str {
+    "ok"
+}
+
+#[pagetop::test]
+async fn rate_limit_works() {
+    let app = service::test::init_service(Application::prepare(&RateLimited).unwrap().test()).await;
+    for _ in 0..2 {
+        let req = service::test::TestRequest::get()
+            .uri("/limited")
+            .to_request();
+        let resp = service::test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+    let req = service::test::TestRequest::get()
+        .uri("/limited")
+        .to_request();
+    let resp = service::test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), service::http::StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp
+        .headers()
+        .contains_key(service::http::header::RETRY_AFTER));
+}
@@ -0,0 +1,190 @@
+use pagetop::prelude::*;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static PREPARED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Default)]
+struct Counter;
+
+impl_handle!(COMPONENT_TEST_SERVER_COUNTER for Counter);
+
+impl ComponentTrait for Counter {
+    fn new() -> Self {
+        Counter
+    }
+
+    fn render_cache(&self) -> Option<RenderCache> {
+        Some(RenderCache::new().with_tag("counter"))
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        cx.alter(ContextOp::AddJavaScript(JavaScript::at("/counter.js")));
+        let prepared = PREPARED.fetch_add(1, Ordering::SeqCst) + 1;
+        PrepareMarkup::With(html! { p { "Prepared " (prepared) } })
+    }
+}
+
+// Componente en caché que genera un identificador y cambia un parámetro del contexto.
+#[derive(Default)]
+struct Tagged;
+
+impl_handle!(COMPONENT_TEST_SERVER_TAGGED for Tagged);
+
+impl ComponentTrait for Tagged {
+    fn new() -> Self {
+        Tagged
+    }
+
+    fn render_cache(&self) -> Option<RenderCache> {
+        Some(RenderCache::new().with_tag("tagged"))
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        cx.set_param("test-tagged", true);
+        let id = cx.required_id::<Tagged>(None);
+        PrepareMarkup::With(html! { p id=(id) { "Tagged" } })
+    }
+}
+
+// Componente sin caché que muestra el parámetro cambiado por `Tagged`.
+#[derive(Default)]
+struct Param;
+
+impl_handle!(COMPONENT_TEST_SERVER_PARAM for Param);
+
+impl ComponentTrait for Param {
+    fn new() -> Self {
+        Param
+    }
+
+    fn prepare_component(&self, cx: &mut Context) -> PrepareMarkup {
+        let tagged = cx.get_param::<bool>("test-tagged").unwrap_or_default();
+        PrepareMarkup::With(html! { p { "Tagged param " (tagged) } })
+    }
+}
+
+// Tema que guarda en caché la región de contenido.
+struct CachedRegions;
+
+impl_handle!(THEME_TEST_SERVER_CACHED_REGIONS for CachedRegions);
+
+impl ModuleTrait for CachedRegions {
+    fn theme(&self) -> Option<ThemeRef> {
+        Some(&CachedRegions)
+    }
+}
+
+impl ThemeTrait for CachedRegions {
+    fn region_cache(&self, region: &str) -> Option<RenderCache> {
+        match region {
+            "content" => Some(RenderCache::new().with_context(CacheContext::Route)),
+            _ => None,
+        }
+    }
+}
+
+struct RenderCached;
+
+impl_handle!(MODULE_TEST_SERVER_RENDER_CACHE for RenderCached);
+
+impl ModuleTrait for RenderCached {
+    fn dependencies(&self) -> Vec<ModuleRef> {
+        vec![&CachedRegions]
+    }
+
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/cached" => cached);
+        service_for_route!(scfg, get "/cached/tagged" => tagged);
+        service_for_route!(scfg, get "/cached/form" => form);
+    }
+}
+
+async fn cached(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request)
+        .with_in("content", Counter::new())
+        .render()
+}
+
+async fn tagged(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request)
+        .with_in("content", Tagged::new())
+        .with_in("content", Tagged::new())
+        .with_in("content", Param::new())
+        .render()
+}
+
+async fn form(request: service::HttpRequest) -> ResultPage<Markup, FatalError> {
+    Page::new(request)
+        .with_context(ContextOp::Theme("CachedRegions"))
+        .with_in("content", Form::new().with_action("/csrf"))
+        .render()
+}
+
+macro_rules! get_body {
+    ( $app:ident, $uri:literal ) => {{
+        let req = service::test::TestRequest::get().uri($uri).to_request();
+        let body = service::test::call_and_read_body(&$app, req).await;
+        String::from_utf8(body.to_vec()).unwrap()
+    }};
+}
+
+#[pagetop::test]
+async fn cached_render_replays_assets_until_invalidated() {
+    let app =
+        service::test::init_service(Application::prepare(&RenderCached).unwrap().test()).await;
+
+    for expected in ["Prepared 1", "Prepared 1", "Prepared 2"] {
+        if expected == "Prepared 2" {
+            invalidate_cache_tags(&["counter"]);
+        }
+        let req = service::test::TestRequest::get()
+            .uri("/cached")
+            .to_request();
+        let body = service::test::call_and_read_body(&app, req).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.contains(expected));
+        assert!(body.contains("/counter.js"));
+    }
+}
+
+#[pagetop::test]
+async fn cached_render_replays_params_and_keeps_ids_unique() {
+    let app =
+        service::test::init_service(Application::prepare(&RenderCached).unwrap().test()).await;
+
+    for _ in 0..2 {
+        let body = get_body!(app, "/cached/tagged");
+        let mut ids: Vec<&str> = body
+            .split(r#"<p id=""#)
+            .skip(1)
+            .map(|rest| rest.split('"').next().unwrap())
+            .collect();
+        ids.dedup();
+
+        assert_eq!(ids.len(), 2);
+        assert!(body.contains("Tagged param true"));
+    }
+}
+
+#[pagetop::test]
+async fn cached_region_does_not_share_csrf_tokens() {
+    let app =
+        service::test::init_service(Application::prepare(&RenderCached).unwrap().test()).await;
+
+    // Cada petición sin cookie abre una sesión nueva.
+    let field = "name=\"csrf_token\" value=\"";
+    let tokens: Vec<String> = [
+        get_body!(app, "/cached/form"),
+        get_body!(app, "/cached/form"),
+    ]
+    .iter()
+    .map(|body| {
+        let start = body.find(field).unwrap() + field.len();
+        body[start..].split('"').next().unwrap().to_owned()
+    })
+    .collect();
+
+    assert_ne!(tokens[0], tokens[1]);
+}
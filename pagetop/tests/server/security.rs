@@ -0,0 +1,61 @@
+use pagetop::prelude::*;
+
+use pagetop::service::http::header;
+
+// Las sustituciones para estas rutas se definen en "config/default.toml".
+struct Security;
+
+impl_handle!(MODULE_TEST_SERVER_SECURITY for Security);
+
+impl ModuleTrait for Security {
+    fn configure_service(&self, scfg: &mut service::web::ServiceConfig) {
+        service_for_route!(scfg, get "/security-test" => ok);
+        service_for_route!(scfg, get "/security-test/page" => ok);
+        service_for_route!(scfg, get "/security-test/embed/page" => ok);
+        service_for_route!(scfg, get "/security-testing" => ok);
+    }
+}
+
+async fn ok() -> &'static str {
+    "ok"
+}
+
+macro_rules! headers {
+    ( $app:expr, $uri:expr ) => {{
+        let req = service::test::TestRequest::get().uri($uri).to_request();
+        let resp = service::test::call_service(&$app, req).await;
+        assert!(resp.status().is_success());
+        resp.headers().clone()
+    }};
+}
+
+#[pagetop::test]
+async fn security_route_overrides_apply_to_prefix_and_subpaths() {
+    let app = service::test::init_service(Application::prepare(&Security).unwrap().test()).await;
+
+    for uri in ["/security-test", "/security-test/page"] {
+        let headers = headers!(app, uri);
+        assert_eq!(headers.get(header::X_FRAME_OPTIONS).unwrap(), "DENY");
+        assert!(headers.contains_key(header::CONTENT_SECURITY_POLICY));
+    }
+}
+
+#[pagetop::test]
+async fn security_route_overrides_use_the_longest_prefix() {
+    let app = service::test::init_service(Application::prepare(&Security).unwrap().test()).await;
+
+    let headers = headers!(app, "/security-test/embed/page");
+    assert!(!headers.contains_key(header::X_FRAME_OPTIONS));
+    assert!(!headers.contains_key(header::CONTENT_SECURITY_POLICY));
+}
+
+#[pagetop::test]
+async fn security_route_overrides_ignore_partial_segments() {
+    let app = service::test::init_service(Application::prepare(&Security).unwrap().test()).await;
+
+    let headers = headers!(app, "/security-testing");
+    assert_eq!(
+        headers.get(header::X_FRAME_OPTIONS).unwrap(),
+        config::SETTINGS.security.x_frame_options.as_str()
+    );
+}